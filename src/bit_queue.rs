@@ -0,0 +1,285 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{
+	cmp::min,
+	io::{ Read, Write, Result as IoResult, Error as IoError, ErrorKind as IoErrorKind }
+};
+
+
+/// The order in which the bits of a byte are pushed and popped
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BitOrder {
+	/// The most significant bit of a byte is the first bit (e.g. JPEG, H.264)
+	MsbFirst,
+	/// The least significant bit of a byte is the first bit (e.g. DEFLATE)
+	LsbFirst
+}
+
+
+/// Creates a mask for the lowest `n` bits (`1 <= n <= 8`)
+fn mask(n: u8) -> u8 {
+	0xff >> (8 - n)
+}
+
+
+/// A queue that allows you to push and pop an arbitrary amount of bits
+///
+/// The complete bytes are stored in a `SliceQueue<u8>`; the partially consumed first byte and the
+/// partially filled last byte are tracked separately.
+#[derive(Clone, Debug)]
+pub struct BitQueue {
+	bytes: SliceQueue<u8>,
+	order: BitOrder,
+	head_offset: u8,
+	tail: u8,
+	tail_len: u8
+}
+impl BitQueue {
+	/// Creates a new `BitQueue`
+	///
+	/// Parameters:
+	///  - `order`: The bit order to use
+	///
+	/// Returns __the new `BitQueue`__
+	pub fn new(order: BitOrder) -> Self {
+		Self::from_bytes(SliceQueue::new(), order)
+	}
+	/// Creates a new `BitQueue` with a predefined limit (the default limit is `usize::MAX`)
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `order`: The bit order to use
+	///  - `limit`: The maximum amount of __bytes__ that can be stored by `self` (a partially
+	///    filled last byte counts towards the limit)
+	///
+	/// Returns __the new `BitQueue`__
+	pub fn with_limit(order: BitOrder, limit: usize) -> Self {
+		Self::from_bytes(SliceQueue::with_limit(limit), order)
+	}
+	/// Creates a new `BitQueue` that starts with the bytes in `bytes`
+	///
+	/// Parameters:
+	///  - `bytes`: The byte queue to use as backing (the limit is preserved)
+	///  - `order`: The bit order to use
+	///
+	/// Returns __the new `BitQueue`__
+	pub fn from_bytes(bytes: SliceQueue<u8>, order: BitOrder) -> Self {
+		BitQueue{ bytes, order, head_offset: 0, tail: 0, tail_len: 0 }
+	}
+	/// Converts `self` back into a byte queue
+	///
+	/// Returns either __`Ok(bytes)`__ or __`Err(self)`__ if `self` is not byte-aligned
+	pub fn into_bytes(self) -> Result<SliceQueue<u8>, Self> {
+		match self.is_aligned() {
+			true => Ok(self.bytes),
+			false => Err(self)
+		}
+	}
+	
+	
+	/// The bit order used by `self`
+	///
+	/// Returns __the bit order__
+	pub fn order(&self) -> BitOrder {
+		self.order
+	}
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of __bytes__ that can be stored by `self`
+	pub fn set_limit(&mut self, limit: usize) {
+		self.bytes.set_limit(limit)
+	}
+	/// The current limit
+	///
+	/// Returns __the maximum amount of bytes that can be stored by `self`__
+	pub fn limit(&self) -> usize {
+		self.bytes.limit()
+	}
+	
+	
+	/// The amount of bits stored
+	///
+	/// Returns __the amount of bits stored in `self`__
+	pub fn len_bits(&self) -> usize {
+		(self.bytes.len() * 8 + self.tail_len as usize) - self.head_offset as usize
+	}
+	/// Checks if there are __no__ bits stored
+	///
+	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
+	pub fn is_empty(&self) -> bool {
+		self.len_bits() == 0
+	}
+	/// The amount of bits that can be pushed until the limit is reached
+	///
+	/// Returns __the amount of bits remaining until the limit is reached__
+	pub fn remaining_bits(&self) -> usize {
+		self.bytes.remaining().saturating_mul(8).saturating_sub(self.tail_len as usize)
+	}
+	/// Checks if both ends of `self` are at a byte boundary
+	///
+	/// Returns either __`true`__ if `self` is byte-aligned or __`false`__ otherwise
+	pub fn is_aligned(&self) -> bool {
+		self.head_offset == 0 && self.tail_len == 0
+	}
+	
+	
+	/// Appends the lowest `n` bits of `value` at the end
+	///
+	/// __Warning: Panics if `n` is greater than `64`__
+	///
+	/// Parameters:
+	///  - `value`: The value containing the bits to append
+	///  - `n`: The amount of bits to append
+	///
+	/// Returns either __`Ok(())`__ if the bits were appended or __`Err(value)`__ if they were not
+	/// appended because the limit would have been exceeded
+	pub fn push_bits(&mut self, value: u64, n: u8) -> Result<(), u64> {
+		assert!(n <= 64, "Cannot push more than 64 bits at once");
+		if (self.tail_len as usize + n as usize).div_ceil(8) > self.bytes.remaining() { return Err(value) }
+		
+		let mut left = n;
+		while left > 0 {
+			// Take as many bits as fit into the last byte
+			let k = min(8 - self.tail_len, left);
+			match self.order {
+				BitOrder::MsbFirst => {
+					let chunk = (value >> (left - k)) as u8 & mask(k);
+					self.tail |= chunk << (8 - self.tail_len - k)
+				},
+				BitOrder::LsbFirst => {
+					let chunk = (value >> (n - left)) as u8 & mask(k);
+					self.tail |= chunk << self.tail_len
+				}
+			}
+			self.tail_len += k;
+			left -= k;
+			
+			// Move the last byte into the byte queue if it is complete
+			if self.tail_len == 8 {
+				self.bytes.push(self.tail).unwrap_or_else(|_| unreachable!("The byte fits into the queue"));
+				self.tail = 0;
+				self.tail_len = 0;
+			}
+		}
+		Ok(())
+	}
+	/// Appends zero bits until the end of `self` is at a byte boundary
+	///
+	/// Returns either __`Ok(padding)`__ with the amount of bits appended or __`Err(padding)`__ with
+	/// the amount of bits that could not be appended because the limit would have been exceeded
+	pub fn pad_to_byte(&mut self) -> Result<u8, u8> {
+		let padding = (8 - self.tail_len) % 8;
+		self.push_bits(0, padding).map(|_| padding).map_err(|_| padding)
+	}
+	
+	
+	/// Take a look at the first `n` bits __without__ consuming them
+	///
+	/// __Warning: Panics if `n` is greater than `64`__
+	///
+	/// Parameters:
+	///  - `n`: The amount of bits to peek at
+	///
+	/// Returns either __`Some(value)`__ if there are at least `n` bits or __`None`__ otherwise
+	pub fn peek_bits(&self, n: u8) -> Option<u64> {
+		assert!(n <= 64, "Cannot peek at more than 64 bits at once");
+		if n as usize > self.len_bits() { return None }
+		
+		let (mut value, mut position, mut left) = (0u64, self.head_offset as usize, n);
+		while left > 0 {
+			// Get the current byte (which is the partially filled last byte after the complete bytes)
+			let (index, offset) = (position / 8, (position % 8) as u8);
+			let byte = if index < self.bytes.len() { self.bytes[index] }
+				else { self.tail };
+			
+			// Take as many bits as are available in the current byte
+			let k = min(8 - offset, left);
+			match self.order {
+				BitOrder::MsbFirst => value = (value << k) | ((byte >> (8 - offset - k)) & mask(k)) as u64,
+				BitOrder::LsbFirst => value |= (((byte >> offset) & mask(k)) as u64) << (n - left)
+			}
+			position += k as usize;
+			left -= k;
+		}
+		Some(value)
+	}
+	/// Consumes the first `n` bits and returns them
+	///
+	/// __Warning: Panics if `n` is greater than `64`__
+	///
+	/// Parameters:
+	///  - `n`: The amount of bits to consume
+	///
+	/// Returns either __`Some(value)`__ if there were at least `n` bits or __`None`__ otherwise
+	pub fn pop_bits(&mut self, n: u8) -> Option<u64> {
+		let value = self.peek_bits(n)?;
+		self.discard_bits(n as usize);
+		Some(value)
+	}
+	/// Discards the remaining bits of the first byte so that the front of `self` is at a byte
+	/// boundary
+	///
+	/// _Info: If the first byte is also the partially filled last byte, only the bits pushed so far
+	/// are discarded; `self` is empty and byte-aligned afterwards._
+	///
+	/// Returns __the amount of bits discarded__
+	pub fn skip_to_byte(&mut self) -> u8 {
+		let available = match self.bytes.is_empty() {
+			true => self.tail_len - self.head_offset,
+			false if self.head_offset > 0 => 8 - self.head_offset,
+			false => 0
+		};
+		self.discard_bits(available as usize);
+		available
+	}
+	
+	/// Discards the first `n` bits (`n` must not be greater than `self.len_bits()`)
+	fn discard_bits(&mut self, n: usize) {
+		let position = self.head_offset as usize + n;
+		let index = min(position / 8, self.bytes.len());
+		self.bytes.drop_n(index).unwrap_or_else(|_| unreachable!("The bytes are buffered completely"));
+		self.head_offset = (position - index * 8) as u8;
+		
+		// Reset the partially filled last byte if it has been consumed completely
+		if self.bytes.is_empty() && self.head_offset == self.tail_len {
+			self.head_offset = 0;
+			self.tail = 0;
+			self.tail_len = 0;
+		}
+	}
+}
+impl Read for BitQueue {
+	/// Pull some bytes from this source into the specified buffer, returning how many bytes were
+	/// read.
+	///
+	/// _Info: Only complete bytes are read; a partially filled last byte remains in `self`._
+	///
+	/// __This call fails if the front of `self` is not byte-aligned__
+	fn read(&mut self, buf: &mut[u8]) -> IoResult<usize> {
+		match self.head_offset {
+			0 => self.bytes.read(buf),
+			_ => Err(IoError::new(IoErrorKind::InvalidInput, "The front of the bit queue is not byte-aligned"))
+		}
+	}
+}
+impl Write for BitQueue {
+	/// Write a buffer into this object, returning how many bytes were written.
+	///
+	/// __This call fails if the end of `self` is not byte-aligned__
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		match self.tail_len {
+			0 => self.bytes.write(buf),
+			_ => Err(IoError::new(IoErrorKind::InvalidInput, "The end of the bit queue is not byte-aligned"))
+		}
+	}
+	/// __This call does nothing (and thus never fails); it is only provided for
+	/// trait-compatibility__
+	fn flush(&mut self) -> IoResult<()> {
+		Ok(())
+	}
+}
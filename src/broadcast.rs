@@ -0,0 +1,379 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{ cmp::min, collections::BTreeMap };
+
+
+/// The policy that is applied if a push would exceed the limit because of slow subscribers
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OverflowPolicy {
+	/// Rejects the elements that do not fit (like `SliceQueue`)
+	///
+	/// __This policy is the default value__
+	Reject,
+	/// Unsubscribes the slowest subscribers until the elements fit
+	DropSlowest,
+	/// Moves the slowest subscribers forward until the elements fit (the skipped elements are
+	/// reported by `BroadcastReader::lagged`)
+	Lag
+}
+
+
+/// An opaque subscriber identifier
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct SubscriberId(u64);
+
+
+/// The read position of a subscriber
+#[derive(Copy, Clone, Debug)]
+struct Cursor {
+	position: u64,
+	lagged: u64
+}
+
+
+/// A queue with one writer and many independent subscribers that have their own read positions
+///
+/// The elements are stored once; every element is freed as soon as all subscribers have consumed
+/// it (or the slow subscribers have been dropped or moved forward according to the overflow
+/// policy). The limit bounds the amount of elements that are retained for the slowest subscriber.
+///
+/// Example:
+/// ```
+/// # extern crate slice_queue;
+/// # use slice_queue::*;
+/// let mut queue = BroadcastSliceQueue::new();
+/// let (a, b) = (queue.subscribe(), queue.subscribe());
+/// queue.push_from(b"Testolope").unwrap();
+///
+/// assert_eq!(queue.reader(a).unwrap().pop_n(4).unwrap(), b"Test");
+/// assert_eq!(queue.reader(b).unwrap().pop_n(9).unwrap(), b"Testolope");
+/// assert_eq!(queue.len(), 5);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BroadcastSliceQueue<T> {
+	queue: SliceQueue<T>,
+	subscribers: BTreeMap<SubscriberId, Cursor>,
+	next_id: u64,
+	policy: OverflowPolicy,
+	limit: usize
+}
+impl<T> BroadcastSliceQueue<T> {
+	/// Creates a new `BroadcastSliceQueue`
+	///
+	/// Returns __the new `BroadcastSliceQueue`__
+	pub fn new() -> Self {
+		Self::with_limit(usize::MAX, OverflowPolicy::Reject)
+	}
+	/// Creates a new `BroadcastSliceQueue` with a predefined limit (the default limit is
+	/// `usize::MAX`) and overflow policy
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that are retained for the slowest subscriber
+	///  - `policy`: The policy that is applied if the limit would be exceeded
+	///
+	/// Returns __the new `BroadcastSliceQueue`__
+	pub fn with_limit(limit: usize, policy: OverflowPolicy) -> Self {
+		assert!(limit > 0, "`limit` is `0`");
+		BroadcastSliceQueue{ queue: SliceQueue::new(), subscribers: BTreeMap::new(), next_id: 0, policy, limit }
+	}
+	
+	
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that are retained for the slowest subscriber
+	pub fn set_limit(&mut self, limit: usize) {
+		assert!(limit > 0, "`limit` is `0`");
+		self.limit = limit
+	}
+	/// The current limit
+	///
+	/// Returns __the maximum amount of elements that are retained for the slowest subscriber__
+	pub fn limit(&self) -> usize {
+		self.limit
+	}
+	/// Sets a new overflow policy (the default policy is `OverflowPolicy::Reject`)
+	///
+	/// Parameters:
+	///  - `policy`: The policy that is applied if the limit would be exceeded
+	pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+		self.policy = policy
+	}
+	/// The current overflow policy
+	///
+	/// Returns __the policy that is applied if the limit would be exceeded__
+	pub fn overflow_policy(&self) -> OverflowPolicy {
+		self.policy
+	}
+	
+	/// The amount of elements that are retained for the slowest subscriber
+	///
+	/// Returns __the amount of elements stored in `self`__
+	pub fn len(&self) -> usize {
+		self.queue.len()
+	}
+	/// Checks if there are __no__ elements retained
+	///
+	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
+	pub fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+	/// The amount of elements that can be pushed until the limit is reached
+	///
+	/// Returns __the amount of elements remaining until the limit is reached__
+	pub fn remaining(&self) -> usize {
+		self.limit.saturating_sub(self.len())
+	}
+	/// The total amount of elements pushed (this is also the absolute offset of the next element)
+	///
+	/// Returns __the total amount of elements pushed__
+	pub fn pushed_total(&self) -> u64 {
+		self.queue.pushed_total()
+	}
+	
+	
+	/// Adds a new subscriber that receives all elements that are pushed after this call
+	///
+	/// Returns __the identifier of the new subscriber__
+	pub fn subscribe(&mut self) -> SubscriberId {
+		let id = SubscriberId(self.next_id);
+		self.next_id += 1;
+		self.subscribers.insert(id, Cursor{ position: self.pushed_total(), lagged: 0 });
+		id
+	}
+	/// Removes a subscriber and frees the elements that are no longer needed
+	///
+	/// Parameters:
+	///  - `id`: The identifier of the subscriber to remove
+	///
+	/// Returns either __`true`__ if the subscriber was removed or __`false`__ if it did not exist (or
+	/// has already been dropped by the overflow policy)
+	pub fn unsubscribe(&mut self, id: SubscriberId) -> bool {
+		let removed = self.subscribers.remove(&id).is_some();
+		self.release();
+		removed
+	}
+	/// Checks if a subscriber exists
+	///
+	/// Parameters:
+	///  - `id`: The identifier of the subscriber
+	///
+	/// Returns either __`true`__ if the subscriber exists or __`false`__ if it has been removed or
+	/// dropped by the overflow policy
+	pub fn is_subscribed(&self, id: SubscriberId) -> bool {
+		self.subscribers.contains_key(&id)
+	}
+	/// The amount of subscribers
+	///
+	/// Returns __the amount of subscribers__
+	pub fn subscriber_count(&self) -> usize {
+		self.subscribers.len()
+	}
+	/// Gets the reader of a subscriber
+	///
+	/// Parameters:
+	///  - `id`: The identifier of the subscriber
+	///
+	/// Returns either __`Some(reader)`__ or __`None`__ if the subscriber has been removed or dropped by
+	/// the overflow policy
+	pub fn reader(&mut self, id: SubscriberId) -> Option<BroadcastReader<'_, T>> {
+		if self.subscribers.contains_key(&id) { Some(BroadcastReader{ queue: self, id }) }
+			else { None }
+	}
+	
+	
+	/// Appends `element` at the end
+	///
+	/// Parameters:
+	///  - `element`: The element to append at the end
+	///
+	/// Returns either __`Ok(())`__ if the element was pushed successfully or __`Err(element)`__ if
+	/// `element` was not appended because the limit would have been exceeded (only with
+	/// `OverflowPolicy::Reject`)
+	pub fn push(&mut self, element: T) -> Result<(), T> {
+		match self.accepted(1) {
+			1 => {
+				self.queue.push(element).unwrap_or_else(|_| unreachable!("The queue is unlimited"));
+				self.enforce_limit();
+				Ok(())
+			},
+			_ => Err(element)
+		}
+	}
+	/// Appends `n` at the end
+	///
+	/// Parameters:
+	///  - `n`: The n elements to append at the end
+	///
+	/// Returns either __`Ok(())`__ if `n` was appended completely or __`Err(remaining_elements)`__
+	/// if `n` was only appended partially because the limit would have been exceeded (only with
+	/// `OverflowPolicy::Reject`)
+	pub fn push_n(&mut self, mut n: Vec<T>) -> Result<(), Vec<T>> {
+		let remaining = n.split_off(self.accepted(n.len()));
+		self.queue.push_n(n).unwrap_or_else(|_| unreachable!("The queue is unlimited"));
+		self.enforce_limit();
+		
+		if remaining.is_empty() { Ok(()) }
+			else { Err(remaining) }
+	}
+	
+	
+	/// The amount of elements out of `n` that are accepted by the overflow policy
+	fn accepted(&self, n: usize) -> usize {
+		match self.policy {
+			OverflowPolicy::Reject => min(n, self.remaining()),
+			_ => n
+		}
+	}
+	/// Drops or moves the slowest subscribers according to the overflow policy until the limit is
+	/// satisfied
+	fn enforce_limit(&mut self) {
+		let min_position = self.pushed_total().saturating_sub(self.limit as u64);
+		match self.policy {
+			OverflowPolicy::Reject => (),
+			OverflowPolicy::DropSlowest => self.subscribers.retain(|_, cursor| cursor.position >= min_position),
+			OverflowPolicy::Lag => self.subscribers.values_mut().filter(|cursor| cursor.position < min_position)
+				.for_each(|cursor| {
+					cursor.lagged += min_position - cursor.position;
+					cursor.position = min_position;
+				})
+		}
+		self.release();
+	}
+	/// Frees all elements that have been consumed by all subscribers
+	fn release(&mut self) {
+		let slowest = self.subscribers.values().map(|cursor| cursor.position).min();
+		self.queue.drop_until_abs(slowest.unwrap_or_else(|| self.pushed_total()))
+			.unwrap_or_else(|_| unreachable!("The cursors are not beyond the end"));
+	}
+}
+impl<T: Clone> BroadcastSliceQueue<T> {
+	/// Clones and appends the elements in `src` at the end
+	///
+	/// Parameters:
+	///  - `src`: A slice containing the elements to clone and append
+	///
+	/// Returns either __`Ok(())`__ if `src` was appended completely or __`Err(appended_count)`__ if
+	/// `src` was only appended partially because the limit would have been exceeded (only with
+	/// `OverflowPolicy::Reject`)
+	pub fn push_from(&mut self, src: &[T]) -> Result<(), usize> {
+		let accepted = self.accepted(src.len());
+		self.queue.push_from(&src[..accepted]).unwrap_or_else(|_| unreachable!("The queue is unlimited"));
+		self.enforce_limit();
+		
+		if accepted == src.len() { Ok(()) }
+			else { Err(accepted) }
+	}
+}
+impl<T> Default for BroadcastSliceQueue<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+
+/// The read access of a single subscriber of a `BroadcastSliceQueue`
+///
+/// _Info: The consuming calls clone the elements because they may still be needed by other
+/// subscribers._
+#[derive(Debug)]
+pub struct BroadcastReader<'a, T: 'a> {
+	queue: &'a mut BroadcastSliceQueue<T>,
+	id: SubscriberId
+}
+impl<'a, T: 'a> BroadcastReader<'a, T> {
+	/// The identifier of the subscriber
+	///
+	/// Returns __the identifier of the subscriber__
+	pub fn id(&self) -> SubscriberId {
+		self.id
+	}
+	/// The total amount of elements the subscriber has consumed (this is also its absolute read
+	/// position)
+	///
+	/// Returns __the absolute offset of the next element to read__
+	pub fn position(&self) -> u64 {
+		self.cursor().position
+	}
+	/// The total amount of elements the subscriber has skipped because of `OverflowPolicy::Lag`
+	///
+	/// Returns __the amount of skipped elements__
+	pub fn lagged(&self) -> u64 {
+		self.cursor().lagged
+	}
+	
+	
+	/// The cursor of the subscriber
+	fn cursor(&self) -> &Cursor {
+		self.queue.subscribers.get(&self.id).unwrap_or_else(|| unreachable!("The subscriber exists"))
+	}
+	/// The unread elements of the subscriber
+	fn unread(&self) -> &[T] {
+		let range = self.position() .. self.queue.pushed_total();
+		self.queue.queue.peek_abs(range).unwrap_or_else(|| unreachable!("The unread elements are retained"))
+	}
+	/// Moves the read position forward by `n` elements and frees the elements that are no longer
+	/// needed
+	fn advance(&mut self, n: usize) {
+		let id = self.id;
+		self.queue.subscribers.get_mut(&id).unwrap_or_else(|| unreachable!("The subscriber exists")).position += n as u64;
+		self.queue.release();
+	}
+}
+impl<'a, T: Clone + 'a> ReadableSliceQueue<T> for BroadcastReader<'a, T> {
+	fn len(&self) -> usize {
+		self.unread().len()
+	}
+	fn is_empty(&self) -> bool {
+		self.unread().is_empty()
+	}
+	fn limit(&self) -> usize {
+		self.queue.limit
+	}
+	
+	fn peek(&self) -> Option<&T> {
+		self.unread().first()
+	}
+	fn peek_n(&self, n: usize) -> Result<&[T], &[T]> {
+		let unread = self.unread();
+		if n <= unread.len() { Ok(&unread[..n]) }
+			else { Err(unread) }
+	}
+	
+	fn pop(&mut self) -> Result<T, ()> {
+		let element = self.peek().cloned().ok_or(())?;
+		self.advance(1);
+		Ok(element)
+	}
+	fn pop_n(&mut self, n: usize) -> Result<Vec<T>, Vec<T>> {
+		let (elements, complete) = match self.peek_n(n) {
+			Ok(elements) => (elements.to_vec(), true),
+			Err(elements) => (elements.to_vec(), false)
+		};
+		self.advance(elements.len());
+		
+		if complete { Ok(elements) }
+			else { Err(elements) }
+	}
+	fn pop_into(&mut self, dst: &mut[T]) -> Result<(), usize> {
+		let to_move = min(dst.len(), self.len());
+		dst[..to_move].clone_from_slice(&self.unread()[..to_move]);
+		self.advance(to_move);
+		
+		if to_move == dst.len() { Ok(()) }
+			else { Err(to_move) }
+	}
+	
+	fn drop_n(&mut self, n: usize) -> Result<(), usize> {
+		let to_drop = min(n, self.len());
+		self.advance(to_drop);
+		
+		if to_drop == n { Ok(()) }
+			else { Err(to_drop) }
+	}
+}
@@ -0,0 +1,236 @@
+use super::{ ReadableSliceQueue, WriteableSliceQueue };
+use std::{ error::Error, fmt::{ Display, Formatter, Result as FmtResult } };
+
+
+/// An error indicating that not enough bytes are available (yet)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Incomplete {
+	/// The amount of bytes needed
+	pub needed: usize,
+	/// The amount of bytes available
+	pub available: usize
+}
+impl Display for Incomplete {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		write!(f, "Need {} bytes but only {} bytes are available", self.needed, self.available)
+	}
+}
+impl Error for Incomplete {}
+
+
+/// An error that occurred while decoding an LEB128 varint
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum VarintError {
+	/// The varint is not complete yet (nothing has been consumed)
+	Incomplete,
+	/// The varint is longer than the maximum of 10 bytes
+	Overlong,
+	/// The varint's value does not fit into 64 bits
+	Overflow
+}
+impl Display for VarintError {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		match self {
+			VarintError::Incomplete => write!(f, "The varint is incomplete"),
+			VarintError::Overlong => write!(f, "The varint is longer than 10 bytes"),
+			VarintError::Overflow => write!(f, "The varint overflows 64 bits")
+		}
+	}
+}
+impl Error for VarintError {}
+
+
+/// Generates a `peek_*_at`- and a `pop_*`-method for a primitive type
+macro_rules! read_fns {
+	($ty:ident, $size:expr, $from_bytes:ident, $peek_at:ident, $pop:ident, $desc:expr) => {
+		#[doc = concat!("Reads ", $desc, " at `offset` __without__ consuming anything")]
+		///
+		/// Parameters:
+		///  - `offset`: The offset of the value's first byte
+		///
+		/// Returns either __`Ok(value)`__ or __`Err(incomplete)`__ if not enough bytes are available
+		fn $peek_at(&self, offset: usize) -> Result<$ty, Incomplete> {
+			let mut bytes = [0; $size];
+			bytes.copy_from_slice(self.peek_bytes_at(offset, $size)?);
+			Ok($ty::$from_bytes(bytes))
+		}
+		#[doc = concat!("Consumes ", $desc, " and returns it")]
+		///
+		/// Returns either __`Ok(value)`__ or __`Err(incomplete)`__ if not enough bytes are available
+		/// (nothing is consumed in this case)
+		fn $pop(&mut self) -> Result<$ty, Incomplete> {
+			let value = self.$peek_at(0)?;
+			self.drop_n($size).expect("Failed to discard buffered bytes");
+			Ok(value)
+		}
+	};
+}
+/// Generates a `push_*`-method for a primitive type
+macro_rules! write_fn {
+	($ty:ident, $to_bytes:ident, $push:ident, $desc:expr) => {
+		#[doc = concat!("Appends `value` as ", $desc, " at the end")]
+		///
+		/// Parameters:
+		///  - `value`: The value to append
+		///
+		/// Returns either __`Ok(())`__ if the value was appended or __`Err(value)`__ if it was not
+		/// appended because `self.limit` would have been exceeded
+		fn $push(&mut self, value: $ty) -> Result<(), $ty> {
+			let bytes = value.$to_bytes();
+			if self.remaining() < bytes.len() { return Err(value) }
+			
+			self.push_from(&bytes).expect("Failed to push bytes");
+			Ok(())
+		}
+	};
+}
+
+
+/// Typed accessors for integers and floats stored in a byte queue
+///
+/// This trait is implemented for every `ReadableSliceQueue<u8>`.
+pub trait ReadableByteQueue: ReadableSliceQueue<u8> {
+	/// Take a look at `n` bytes starting at `offset` __without__ consuming them
+	///
+	/// Parameters:
+	///  - `offset`: The offset of the first byte
+	///  - `n`: The amount of bytes to peek at
+	///
+	/// Returns either __`Ok(bytes)`__ or __`Err(incomplete)`__ if not enough bytes are available
+	fn peek_bytes_at(&self, offset: usize, n: usize) -> Result<&[u8], Incomplete> {
+		let needed = offset.saturating_add(n);
+		match self.peek_n(needed) {
+			Ok(bytes) => Ok(&bytes[offset..]),
+			Err(bytes) => Err(Incomplete{ needed, available: bytes.len() })
+		}
+	}
+	
+	/// Decodes the unsigned LEB128 (protobuf) varint at the beginning __without__ consuming it
+	///
+	/// _Info: Like protobuf, non-minimal encodings (e.g. `0x80 0x00` for `0`) are accepted._
+	///
+	/// Returns either __`Ok((value, varint_len))`__ or __`Err(varint_error)`__ if the varint is
+	/// incomplete or invalid
+	fn peek_varint(&self) -> Result<(u64, usize), VarintError> {
+		let bytes = match self.peek_n(10) { Ok(bytes) | Err(bytes) => bytes };
+		let mut value = 0u64;
+		for (i, b) in bytes.iter().enumerate() {
+			// The 10th byte may only contain the 64th bit and must be the last byte
+			match (i, *b) {
+				(9, b) if b & 0x80 != 0 => return Err(VarintError::Overlong),
+				(9, b) if b > 0x01 => return Err(VarintError::Overflow),
+				_ => value |= ((*b & 0x7f) as u64) << (i * 7)
+			}
+			if *b & 0x80 == 0 { return Ok((value, i + 1)) }
+		}
+		Err(VarintError::Incomplete)
+	}
+	/// Consumes an unsigned LEB128 (protobuf) varint and returns it
+	///
+	/// Returns either __`Ok(value)`__ or __`Err(varint_error)`__ if the varint is incomplete or
+	/// invalid (nothing is consumed in this case)
+	fn pop_varint_u64(&mut self) -> Result<u64, VarintError> {
+		let (value, varint_len) = self.peek_varint()?;
+		self.drop_n(varint_len).expect("Failed to discard buffered varint");
+		Ok(value)
+	}
+	/// Consumes a zigzag-encoded signed LEB128 (protobuf `sint64`) varint and returns it
+	///
+	/// Returns either __`Ok(value)`__ or __`Err(varint_error)`__ if the varint is incomplete or
+	/// invalid (nothing is consumed in this case)
+	fn pop_varint_i64(&mut self) -> Result<i64, VarintError> {
+		let value = self.pop_varint_u64()?;
+		Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+	}
+	
+	read_fns!(u8, 1, from_be_bytes, peek_u8_at, pop_u8, "a `u8`");
+	read_fns!(i8, 1, from_be_bytes, peek_i8_at, pop_i8, "an `i8`");
+	read_fns!(u16, 2, from_be_bytes, peek_u16_be_at, pop_u16_be, "a big endian `u16`");
+	read_fns!(u16, 2, from_le_bytes, peek_u16_le_at, pop_u16_le, "a little endian `u16`");
+	read_fns!(i16, 2, from_be_bytes, peek_i16_be_at, pop_i16_be, "a big endian `i16`");
+	read_fns!(i16, 2, from_le_bytes, peek_i16_le_at, pop_i16_le, "a little endian `i16`");
+	read_fns!(u32, 4, from_be_bytes, peek_u32_be_at, pop_u32_be, "a big endian `u32`");
+	read_fns!(u32, 4, from_le_bytes, peek_u32_le_at, pop_u32_le, "a little endian `u32`");
+	read_fns!(i32, 4, from_be_bytes, peek_i32_be_at, pop_i32_be, "a big endian `i32`");
+	read_fns!(i32, 4, from_le_bytes, peek_i32_le_at, pop_i32_le, "a little endian `i32`");
+	read_fns!(u64, 8, from_be_bytes, peek_u64_be_at, pop_u64_be, "a big endian `u64`");
+	read_fns!(u64, 8, from_le_bytes, peek_u64_le_at, pop_u64_le, "a little endian `u64`");
+	read_fns!(i64, 8, from_be_bytes, peek_i64_be_at, pop_i64_be, "a big endian `i64`");
+	read_fns!(i64, 8, from_le_bytes, peek_i64_le_at, pop_i64_le, "a little endian `i64`");
+	read_fns!(u128, 16, from_be_bytes, peek_u128_be_at, pop_u128_be, "a big endian `u128`");
+	read_fns!(u128, 16, from_le_bytes, peek_u128_le_at, pop_u128_le, "a little endian `u128`");
+	read_fns!(i128, 16, from_be_bytes, peek_i128_be_at, pop_i128_be, "a big endian `i128`");
+	read_fns!(i128, 16, from_le_bytes, peek_i128_le_at, pop_i128_le, "a little endian `i128`");
+	read_fns!(f32, 4, from_be_bytes, peek_f32_be_at, pop_f32_be, "a big endian `f32`");
+	read_fns!(f32, 4, from_le_bytes, peek_f32_le_at, pop_f32_le, "a little endian `f32`");
+	read_fns!(f64, 8, from_be_bytes, peek_f64_be_at, pop_f64_be, "a big endian `f64`");
+	read_fns!(f64, 8, from_le_bytes, peek_f64_le_at, pop_f64_le, "a little endian `f64`");
+}
+impl<Q: ReadableSliceQueue<u8> + ?Sized> ReadableByteQueue for Q {}
+
+
+/// Typed writers for integers and floats stored in a byte queue
+///
+/// This trait is implemented for every `WriteableSliceQueue<u8>`.
+pub trait WriteableByteQueue: WriteableSliceQueue<u8> {
+	/// Appends `value` as unsigned LEB128 (protobuf) varint at the end
+	///
+	/// Parameters:
+	///  - `value`: The value to append
+	///
+	/// Returns either __`Ok(())`__ if the varint was appended or __`Err(value)`__ if it was not
+	/// appended because `self.limit` would have been exceeded
+	fn push_varint(&mut self, value: u64) -> Result<(), u64> {
+		// Encode the value
+		let (mut bytes, mut len, mut rest) = ([0u8; 10], 0, value);
+		loop {
+			bytes[len] = (rest & 0x7f) as u8;
+			rest >>= 7;
+			len += 1;
+			
+			match rest {
+				0 => break,
+				_ => bytes[len - 1] |= 0x80
+			}
+		}
+		
+		// Push the encoded value
+		if self.remaining() < len { return Err(value) }
+		self.push_from(&bytes[..len]).expect("Failed to push varint");
+		Ok(())
+	}
+	/// Appends `value` as zigzag-encoded signed LEB128 (protobuf `sint64`) varint at the end
+	///
+	/// Parameters:
+	///  - `value`: The value to append
+	///
+	/// Returns either __`Ok(())`__ if the varint was appended or __`Err(value)`__ if it was not
+	/// appended because `self.limit` would have been exceeded
+	fn push_varint_i64(&mut self, value: i64) -> Result<(), i64> {
+		self.push_varint(((value << 1) ^ (value >> 63)) as u64).map_err(|_| value)
+	}
+	
+	write_fn!(u8, to_be_bytes, push_u8, "a `u8`");
+	write_fn!(i8, to_be_bytes, push_i8, "an `i8`");
+	write_fn!(u16, to_be_bytes, push_u16_be, "a big endian `u16`");
+	write_fn!(u16, to_le_bytes, push_u16_le, "a little endian `u16`");
+	write_fn!(i16, to_be_bytes, push_i16_be, "a big endian `i16`");
+	write_fn!(i16, to_le_bytes, push_i16_le, "a little endian `i16`");
+	write_fn!(u32, to_be_bytes, push_u32_be, "a big endian `u32`");
+	write_fn!(u32, to_le_bytes, push_u32_le, "a little endian `u32`");
+	write_fn!(i32, to_be_bytes, push_i32_be, "a big endian `i32`");
+	write_fn!(i32, to_le_bytes, push_i32_le, "a little endian `i32`");
+	write_fn!(u64, to_be_bytes, push_u64_be, "a big endian `u64`");
+	write_fn!(u64, to_le_bytes, push_u64_le, "a little endian `u64`");
+	write_fn!(i64, to_be_bytes, push_i64_be, "a big endian `i64`");
+	write_fn!(i64, to_le_bytes, push_i64_le, "a little endian `i64`");
+	write_fn!(u128, to_be_bytes, push_u128_be, "a big endian `u128`");
+	write_fn!(u128, to_le_bytes, push_u128_le, "a little endian `u128`");
+	write_fn!(i128, to_be_bytes, push_i128_be, "a big endian `i128`");
+	write_fn!(i128, to_le_bytes, push_i128_le, "a little endian `i128`");
+	write_fn!(f32, to_be_bytes, push_f32_be, "a big endian `f32`");
+	write_fn!(f32, to_le_bytes, push_f32_le, "a little endian `f32`");
+	write_fn!(f64, to_be_bytes, push_f64_be, "a big endian `f64`");
+	write_fn!(f64, to_le_bytes, push_f64_le, "a little endian `f64`");
+}
+impl<Q: WriteableSliceQueue<u8> + ?Sized> WriteableByteQueue for Q {}
@@ -0,0 +1,136 @@
+use super::{ CodecError, Decoder, Encoder, decode_delimited };
+use ::{ ReadableSliceQueue, WriteableSliceQueue };
+use std::ops::{ IndexMut, Range };
+
+
+/// Unstuffs the COBS-encoded `frame` in place
+///
+/// Returns either __`Ok(unstuffed_len)`__ or __`Err(codec_error)`__ if `frame` is not a valid COBS
+/// encoding
+fn unstuff(frame: &mut[u8]) -> Result<usize, CodecError> {
+	let (mut read, mut write) = (0, 0);
+	while read < frame.len() {
+		// Read the code and validate the block length
+		let code = frame[read] as usize;
+		let block_end = read + code;
+		if code == 0 { return Err(CodecError::Malformed("COBS frame contains a zero byte")) }
+		if block_end > frame.len() { return Err(CodecError::Malformed("COBS block exceeds the frame")) }
+		
+		// Move the block and append the implicit zero if this is not the last or a maximum block
+		frame.copy_within(read + 1..block_end, write);
+		write += code - 1;
+		read = block_end;
+		if code != 0xff && read < frame.len() {
+			frame[write] = 0;
+			write += 1;
+		}
+	}
+	Ok(write)
+}
+
+
+/// A codec for COBS ("Consistent Overhead Byte Stuffing") frames that are terminated by a `0x00`
+/// byte
+///
+/// Decoding unstuffs the frame in place within the queue so that no intermediate buffer is needed.
+/// Empty frames (i.e. consecutive delimiters) are skipped; if a frame is invalid, it is discarded
+/// so that the next `decode`-call resynchronizes on the next delimiter.
+#[derive(Copy, Clone, Debug)]
+pub struct CobsCodec {
+	max_frame_len: usize
+}
+impl CobsCodec {
+	/// Creates a new `CobsCodec`
+	///
+	/// Returns __the new `CobsCodec`__
+	pub fn new() -> Self {
+		CobsCodec{ max_frame_len: usize::MAX }
+	}
+	
+	
+	/// Sets the maximum unstuffed frame length (the default maximum is `usize::MAX`)
+	///
+	/// Parameters:
+	///  - `max_frame_len`: The maximum unstuffed frame length to accept
+	pub fn set_max_frame_len(&mut self, max_frame_len: usize) {
+		self.max_frame_len = max_frame_len
+	}
+	/// The maximum unstuffed frame length
+	///
+	/// Returns __the maximum unstuffed frame length__
+	pub fn max_frame_len(&self) -> usize {
+		self.max_frame_len
+	}
+	
+	
+	/// The maximum stuffed length of a frame with `frame_len` bytes
+	fn stuffed_len(frame_len: usize) -> usize {
+		frame_len.saturating_add(frame_len / 254).saturating_add(1)
+	}
+}
+impl Default for CobsCodec {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<Q> Decoder<Q> for CobsCodec where Q: ReadableSliceQueue<u8> + IndexMut<Range<usize>, Output = [u8]> + ?Sized {
+	type Item = Vec<u8>;
+	
+	/// Tries to decode and consume the next frame
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the frame from
+	///
+	/// Returns either __`Ok(Some(frame))`__ if a complete frame was decoded and consumed,
+	/// __`Ok(None)`__ if more data is needed (the queue is left untouched in this case) or
+	/// __`Err(codec_error)`__ if an invalid or oversized frame was discarded
+	fn decode(&mut self, queue: &mut Q) -> Result<Option<Vec<u8>>, CodecError> {
+		decode_delimited(queue, 0x00, self.max_frame_len, Self::stuffed_len(self.max_frame_len), unstuff)
+	}
+}
+impl<Q: WriteableSliceQueue<u8> + ?Sized> Encoder<Q, [u8]> for CobsCodec {
+	/// Encodes `frame` and appends it together with the delimiter at the end of `queue`
+	///
+	/// Parameters:
+	///  - `frame`: The frame to encode
+	///  - `queue`: The queue to push the encoded frame to
+	///
+	/// Returns either __`Ok(())`__ if the entire frame was pushed or __`Err(codec_error)`__ if the
+	/// frame is too large or the queue has not enough space remaining (nothing is pushed in this
+	/// case)
+	fn encode(&mut self, frame: &[u8], queue: &mut Q) -> Result<(), CodecError> {
+		if frame.len() > self.max_frame_len {
+			return Err(CodecError::TooLarge{ size: frame.len(), max: self.max_frame_len })
+		}
+		
+		// Stuff the frame; `code_pos` is the position of the current block's code byte
+		let mut stuffed = Vec::with_capacity(Self::stuffed_len(frame.len()) + 1);
+		let (mut code_pos, mut after_max_block) = (0, false);
+		stuffed.push(0x01);
+		for b in frame {
+			after_max_block = false;
+			if *b != 0x00 {
+				stuffed.push(*b);
+				stuffed[code_pos] += 1;
+			}
+			
+			// Start a new block if we reached a zero byte or the maximum block length
+			if *b == 0x00 || stuffed[code_pos] == 0xff {
+				after_max_block = *b != 0x00;
+				code_pos = stuffed.len();
+				stuffed.push(0x01);
+			}
+		}
+		
+		// Omit the trailing empty block if the frame ends with a maximum block
+		if after_max_block { stuffed.pop(); }
+		stuffed.push(0x00);
+		
+		// Push the stuffed frame if it fits completely
+		if stuffed.len() > queue.remaining() {
+			return Err(CodecError::NoSpace{ needed: stuffed.len(), remaining: queue.remaining() })
+		}
+		queue.push_from(&stuffed).expect("Failed to push stuffed frame");
+		Ok(())
+	}
+}
@@ -0,0 +1,357 @@
+use super::{ CodecError, Decoder };
+use ReadableSliceQueue;
+use std::{ cmp::min, str };
+
+
+/// Finds the first occurrence of `needle` in `haystack`
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+	haystack.windows(needle.len()).position(|window| window == needle)
+}
+/// Removes leading and trailing spaces and horizontal tabs
+fn trim(mut bytes: &[u8]) -> &[u8] {
+	while let Some(b' ') | Some(b'\t') = bytes.first() { bytes = &bytes[1..] }
+	while let Some(b' ') | Some(b'\t') = bytes.last() { bytes = &bytes[..bytes.len() - 1] }
+	bytes
+}
+/// Converts `bytes` into a non-empty `str` that contains neither whitespace nor control characters
+fn token<'a>(bytes: &'a [u8], what: &'static str) -> Result<&'a str, CodecError> {
+	match bytes.is_empty() || bytes.iter().any(|b| *b <= b' ' || *b >= 0x7f) {
+		true => Err(CodecError::Malformed(what)),
+		false => Ok(str::from_utf8(bytes).expect("ASCII is always valid UTF-8"))
+	}
+}
+/// Parses the header lines in `lines` (which must not contain the terminating empty line)
+fn parse_headers(lines: &[u8]) -> Result<Vec<(&str, &[u8])>, CodecError> {
+	let mut headers = Vec::new();
+	for line in lines.split(|b| *b == b'\n').filter(|line| !line.is_empty()) {
+		// Validate the line ending and reject obsolete line folding
+		let line = match line.split_last() {
+			Some((b'\r', line)) => line,
+			_ => return Err(CodecError::Malformed("header line does not end with CRLF"))
+		};
+		if let Some(b' ') | Some(b'\t') = line.first() {
+			return Err(CodecError::Malformed("obsolete header line folding"))
+		}
+		
+		// Split the line into name and value
+		let colon = line.iter().position(|b| *b == b':')
+			.ok_or(CodecError::Malformed("header line without colon"))?;
+		headers.push((token(&line[..colon], "invalid header name")?, trim(&line[colon + 1..])));
+	}
+	Ok(headers)
+}
+
+
+/// The first line of an HTTP/1.x message head
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum StartLine<'a> {
+	/// A request line (e.g. `GET /index.html HTTP/1.1`)
+	Request{ method: &'a str, target: &'a str, version: &'a str },
+	/// A status line (e.g. `HTTP/1.1 200 OK`)
+	Response{ version: &'a str, status: u16, reason: &'a [u8] }
+}
+impl<'a> StartLine<'a> {
+	/// Parses `line` (without the trailing CRLF)
+	fn parse(line: &'a [u8]) -> Result<Self, CodecError> {
+		let mut fields = line.splitn(3, |b| *b == b' ');
+		let (first, second, third) = (fields.next().unwrap_or_default(), fields.next(), fields.next());
+		
+		match (first.starts_with(b"HTTP/"), second, third) {
+			(true, Some(status), reason) => {
+				// Parse the three-digit status code
+				if status.len() != 3 || !status.iter().all(u8::is_ascii_digit) {
+					return Err(CodecError::Malformed("invalid status code"))
+				}
+				let status = status.iter().fold(0, |status, b| status * 10 + (b - b'0') as u16);
+				Ok(StartLine::Response{ version: token(first, "invalid HTTP version")?, status, reason: reason.unwrap_or_default() })
+			},
+			(false, Some(target), Some(version)) if version.starts_with(b"HTTP/") => Ok(StartLine::Request{
+				method: token(first, "invalid request method")?,
+				target: token(target, "invalid request target")?,
+				version: token(version, "invalid HTTP version")?
+			}),
+			_ => Err(CodecError::Malformed("invalid start line"))
+		}
+	}
+}
+
+
+/// A parsed HTTP/1.x message head that borrows from the queue it was decoded from
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HttpHead<'a> {
+	/// The request or status line
+	pub start_line: StartLine<'a>,
+	/// The header fields as `(name, value)`-pairs in the order they were received
+	pub headers: Vec<(&'a str, &'a [u8])>
+}
+impl<'a> HttpHead<'a> {
+	/// Gets the value of the first header with the given name
+	///
+	/// Parameters:
+	///  - `name`: The header name to look for (the comparison is case-insensitive)
+	///
+	/// Returns either __`Some(value)`__ if a header with this name exists or __`None`__ otherwise
+	pub fn header(&self, name: &str) -> Option<&'a [u8]> {
+		self.headers.iter().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, value)| *value)
+	}
+}
+
+
+/// A decoder for HTTP/1.x request and response heads
+///
+/// The head is parsed in place and passed as borrowed `HttpHead` to a callback; afterwards it is
+/// drained from the queue. If the head is invalid or too large, the queue is left untouched and
+/// the connection should be closed.
+#[derive(Copy, Clone, Debug)]
+pub struct HttpHeadDecoder {
+	max_head_len: usize
+}
+impl HttpHeadDecoder {
+	/// Creates a new `HttpHeadDecoder`
+	///
+	/// Returns __the new `HttpHeadDecoder`__
+	pub fn new() -> Self {
+		HttpHeadDecoder{ max_head_len: usize::MAX }
+	}
+	
+	
+	/// Sets the maximum head length including the terminating empty line (the default maximum is
+	/// `usize::MAX`)
+	///
+	/// _Info: Independent of this setting, a head is always rejected if it exceeds the limit of
+	/// the queue it is decoded from._
+	///
+	/// Parameters:
+	///  - `max_head_len`: The maximum head length to accept
+	pub fn set_max_head_len(&mut self, max_head_len: usize) {
+		self.max_head_len = max_head_len
+	}
+	/// The maximum head length
+	///
+	/// Returns __the maximum head length__
+	pub fn max_head_len(&self) -> usize {
+		self.max_head_len
+	}
+	
+	
+	/// Tries to decode the next head, passes it to `head_fn` and drains it
+	///
+	/// Leading empty lines are skipped (see RFC 7230, section 3.5).
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the head from
+	///  - `head_fn`: A callback that is called with the parsed head
+	///
+	/// Returns either __`Ok(Some(head_fn_result))`__ if a complete head was decoded and consumed,
+	/// __`Ok(None)`__ if more data is needed or __`Err(codec_error)`__ if the head is invalid or
+	/// too large
+	pub fn decode_with<Q, R>(&mut self, queue: &mut Q, head_fn: impl FnOnce(&HttpHead) -> R) -> Result<Option<R>, CodecError>
+		where Q: ReadableSliceQueue<u8> + ?Sized
+	{
+		// Skip leading empty lines
+		while let Ok(b"\r\n") = queue.peek_n(2) { queue.drop_n(2).expect("Failed to discard empty line") }
+		
+		// Find the end of the head
+		let max_head_len = min(self.max_head_len, queue.limit());
+		let buffered = match queue.peek_n(queue.len()) { Ok(bytes) | Err(bytes) => bytes };
+		let head_len = match find(&buffered[..min(buffered.len(), max_head_len)], b"\r\n\r\n") {
+			Some(end) => end + 4,
+			None if buffered.len() >= max_head_len => return Err(CodecError::TooLarge{ size: buffered.len(), max: max_head_len }),
+			None => return Ok(None)
+		};
+		
+		// Parse the head and call `head_fn`
+		let start_line_len = find(buffered, b"\r\n").expect("The head contains at least one CRLF");
+		let head = HttpHead{
+			start_line: StartLine::parse(&buffered[..start_line_len])?,
+			headers: parse_headers(&buffered[start_line_len + 2 .. head_len - 2])?
+		};
+		let result = head_fn(&head);
+		
+		// Drain the head
+		queue.drop_n(head_len).expect("Failed to discard head");
+		Ok(Some(result))
+	}
+}
+impl Default for HttpHeadDecoder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+
+/// An item of a chunked transfer-encoded body
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChunkedItem {
+	/// The data of a chunk
+	Chunk(Vec<u8>),
+	/// The end of the body together with the trailer fields as `(name, value)`-pairs
+	End(Vec<(String, Vec<u8>)>)
+}
+
+
+/// The state of a `ChunkedDecoder`
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum ChunkedState {
+	/// Expecting a chunk-size line
+	Size,
+	/// Expecting the chunk data with the given size followed by a CRLF
+	Data(usize),
+	/// Expecting the trailer section after the last chunk
+	Trailers,
+	/// The body has been decoded completely
+	Done
+}
+
+
+/// A decoder for chunked transfer-encoded HTTP/1.x bodies that pops the body chunk by chunk
+///
+/// Chunk extensions are ignored. After the trailer section has been decoded, the decoder returns
+/// `Ok(None)` until it is `reset`.
+#[derive(Copy, Clone, Debug)]
+pub struct ChunkedDecoder {
+	state: ChunkedState,
+	max_chunk_len: usize,
+	max_trailers_len: usize
+}
+impl ChunkedDecoder {
+	/// Creates a new `ChunkedDecoder`
+	///
+	/// Returns __the new `ChunkedDecoder`__
+	pub fn new() -> Self {
+		ChunkedDecoder{ state: ChunkedState::Size, max_chunk_len: usize::MAX, max_trailers_len: usize::MAX }
+	}
+	
+	
+	/// Sets the maximum chunk length (the default maximum is `usize::MAX`)
+	///
+	/// _Info: Independent of this setting, a chunk is always rejected if it (including its
+	/// trailing CRLF) exceeds the limit of the queue it is decoded from._
+	///
+	/// Parameters:
+	///  - `max_chunk_len`: The maximum chunk length to accept
+	pub fn set_max_chunk_len(&mut self, max_chunk_len: usize) {
+		self.max_chunk_len = max_chunk_len
+	}
+	/// The maximum chunk length
+	///
+	/// Returns __the maximum chunk length__
+	pub fn max_chunk_len(&self) -> usize {
+		self.max_chunk_len
+	}
+	
+	/// Sets the maximum length of the trailer section and of each chunk-size line (the default
+	/// maximum is `usize::MAX`)
+	///
+	/// _Info: Independent of this setting, the trailer section and each chunk-size line are always
+	/// rejected if they exceed the limit of the queue they are decoded from._
+	///
+	/// Parameters:
+	///  - `max_trailers_len`: The maximum trailer section and chunk-size line length to accept
+	pub fn set_max_trailers_len(&mut self, max_trailers_len: usize) {
+		self.max_trailers_len = max_trailers_len
+	}
+	/// The maximum length of the trailer section and of each chunk-size line
+	///
+	/// Returns __the maximum trailer section and chunk-size line length__
+	pub fn max_trailers_len(&self) -> usize {
+		self.max_trailers_len
+	}
+	
+	
+	/// Whether the body has been decoded completely
+	///
+	/// Returns __`true`__ if the trailer section has been decoded or __`false`__ otherwise
+	pub fn is_done(&self) -> bool {
+		self.state == ChunkedState::Done
+	}
+	/// Resets the decoder so that it can decode the next body
+	pub fn reset(&mut self) {
+		self.state = ChunkedState::Size
+	}
+}
+impl Default for ChunkedDecoder {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<Q: ReadableSliceQueue<u8> + ?Sized> Decoder<Q> for ChunkedDecoder {
+	type Item = ChunkedItem;
+	
+	/// Tries to decode and consume the next chunk or the trailer section
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the chunk from
+	///
+	/// Returns either __`Ok(Some(item))`__ if a complete chunk or the trailer section was decoded
+	/// and consumed, __`Ok(None)`__ if more data is needed or the body is done or
+	/// __`Err(codec_error)`__ if the chunk is invalid or too large
+	fn decode(&mut self, queue: &mut Q) -> Result<Option<ChunkedItem>, CodecError> {
+		loop {
+			let max_trailers_len = min(self.max_trailers_len, queue.limit());
+			let buffered = match queue.peek_n(queue.len()) { Ok(bytes) | Err(bytes) => bytes };
+			match self.state {
+				ChunkedState::Size => {
+					// Find the end of the size line
+					let line_len = match find(&buffered[..min(buffered.len(), max_trailers_len)], b"\r\n") {
+						Some(end) => end,
+						None if buffered.len() >= max_trailers_len => return Err(CodecError::TooLarge{ size: buffered.len(), max: max_trailers_len }),
+						None => return Ok(None)
+					};
+					
+					// Parse the hexadecimal size and ignore any chunk extensions
+					let size = trim(buffered[..line_len].split(|b| *b == b';').next().unwrap_or_default());
+					if size.is_empty() || size.len() > 16 || !size.iter().all(u8::is_ascii_hexdigit) {
+						return Err(CodecError::Malformed("invalid chunk size"))
+					}
+					let size = size.iter().fold(0u64, |size, b| (size << 4) | (*b as char).to_digit(16).unwrap_or_default() as u64);
+					
+					// Validate the size and consume the line
+					let max_chunk_len = min(self.max_chunk_len, queue.limit().saturating_sub(2));
+					if size > max_chunk_len as u64 {
+						return Err(CodecError::TooLarge{ size: min(size, usize::MAX as u64) as usize, max: max_chunk_len })
+					}
+					queue.drop_n(line_len + 2).expect("Failed to discard chunk-size line");
+					self.state = match size {
+						0 => ChunkedState::Trailers,
+						size => ChunkedState::Data(size as usize)
+					};
+				},
+				ChunkedState::Data(size) => {
+					// Validate the chunk's CRLF and pop the chunk
+					match buffered.get(size .. size + 2) {
+						Some(b"\r\n") => (),
+						Some(_) => return Err(CodecError::Malformed("chunk data does not end with CRLF")),
+						None => return Ok(None)
+					}
+					let chunk = queue.pop_n(size).expect("Failed to pop chunk");
+					queue.drop_n(2).expect("Failed to discard chunk CRLF");
+					
+					self.state = ChunkedState::Size;
+					return Ok(Some(ChunkedItem::Chunk(chunk)))
+				},
+				ChunkedState::Trailers => {
+					// Find the end of the trailer section (which may be empty)
+					let trailers_len = match buffered.starts_with(b"\r\n") {
+						true => 2,
+						false => match find(&buffered[..min(buffered.len(), max_trailers_len)], b"\r\n\r\n") {
+							Some(end) => end + 4,
+							None if buffered.len() >= max_trailers_len => return Err(CodecError::TooLarge{ size: buffered.len(), max: max_trailers_len }),
+							None => return Ok(None)
+						}
+					};
+					
+					// Parse and consume the trailers
+					let trailers = parse_headers(&buffered[..trailers_len - 2])?.into_iter()
+						.map(|(name, value)| (name.to_string(), value.to_vec()))
+						.collect();
+					queue.drop_n(trailers_len).expect("Failed to discard trailers");
+					
+					self.state = ChunkedState::Done;
+					return Ok(Some(ChunkedItem::End(trailers)))
+				},
+				ChunkedState::Done => return Ok(None)
+			}
+		}
+	}
+}
@@ -0,0 +1,269 @@
+use super::{ CodecError, Decoder, Encoder };
+use ::{ ReadableSliceQueue, WriteableSliceQueue, ReadableByteQueue, WriteableByteQueue, VarintError };
+use std::cmp::min;
+
+
+/// The byte order of a multi-byte integer
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ByteOrder {
+	/// The most significant byte comes first (network byte order)
+	BigEndian,
+	/// The least significant byte comes first
+	LittleEndian
+}
+
+
+/// The encoding of the length-header that prefixes each frame
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LengthHeader {
+	/// A single byte
+	U8,
+	/// A 16 bit integer with the given byte order
+	U16(ByteOrder),
+	/// A 32 bit integer with the given byte order
+	U32(ByteOrder),
+	/// A 64 bit integer with the given byte order
+	U64(ByteOrder),
+	/// An unsigned LEB128 varint (up to 10 bytes)
+	Varint
+}
+impl LengthHeader {
+	/// The largest value that can be represented by this header
+	fn max_value(self) -> u64 {
+		match self {
+			LengthHeader::U8 => u8::MAX as u64,
+			LengthHeader::U16(_) => u16::MAX as u64,
+			LengthHeader::U32(_) => u32::MAX as u64,
+			LengthHeader::U64(_) | LengthHeader::Varint => u64::MAX
+		}
+	}
+	
+	/// Reads the header from the beginning of `queue`
+	///
+	/// Returns either __`Ok(Some((value, header_len)))`__ if the header is complete, __`Ok(None)`__
+	/// if more bytes are needed or __`Err(codec_error)`__ if the header is malformed
+	fn read<Q: ReadableSliceQueue<u8> + ?Sized>(self, queue: &Q) -> Result<Option<(u64, usize)>, CodecError> {
+		use self::ByteOrder::*;
+		let header = match self {
+			LengthHeader::U8 => queue.peek_u8_at(0).map(|value| (value as u64, 1)),
+			LengthHeader::U16(BigEndian) => queue.peek_u16_be_at(0).map(|value| (value as u64, 2)),
+			LengthHeader::U16(LittleEndian) => queue.peek_u16_le_at(0).map(|value| (value as u64, 2)),
+			LengthHeader::U32(BigEndian) => queue.peek_u32_be_at(0).map(|value| (value as u64, 4)),
+			LengthHeader::U32(LittleEndian) => queue.peek_u32_le_at(0).map(|value| (value as u64, 4)),
+			LengthHeader::U64(BigEndian) => queue.peek_u64_be_at(0).map(|value| (value, 8)),
+			LengthHeader::U64(LittleEndian) => queue.peek_u64_le_at(0).map(|value| (value, 8)),
+			LengthHeader::Varint => return match queue.peek_varint() {
+				Ok(header) => Ok(Some(header)),
+				Err(VarintError::Incomplete) => Ok(None),
+				Err(VarintError::Overlong) => Err(CodecError::Malformed("varint is longer than 10 bytes")),
+				Err(VarintError::Overflow) => Err(CodecError::Malformed("varint overflows 64 bits"))
+			}
+		};
+		Ok(header.ok())
+	}
+	/// Appends `value` as header at the end of `queue`
+	///
+	/// __Warning: Panics if `queue` has not enough space remaining__
+	fn write<Q: WriteableSliceQueue<u8> + ?Sized>(self, value: u64, queue: &mut Q) {
+		use self::ByteOrder::*;
+		let pushed = match self {
+			LengthHeader::U8 => queue.push_u8(value as u8).is_ok(),
+			LengthHeader::U16(BigEndian) => queue.push_u16_be(value as u16).is_ok(),
+			LengthHeader::U16(LittleEndian) => queue.push_u16_le(value as u16).is_ok(),
+			LengthHeader::U32(BigEndian) => queue.push_u32_be(value as u32).is_ok(),
+			LengthHeader::U32(LittleEndian) => queue.push_u32_le(value as u32).is_ok(),
+			LengthHeader::U64(BigEndian) => queue.push_u64_be(value).is_ok(),
+			LengthHeader::U64(LittleEndian) => queue.push_u64_le(value).is_ok(),
+			LengthHeader::Varint => queue.push_varint(value).is_ok()
+		};
+		assert!(pushed, "Failed to push header")
+	}
+	/// The length of the encoded header for `value`
+	fn len(self, value: u64) -> usize {
+		match self {
+			LengthHeader::U8 => 1,
+			LengthHeader::U16(_) => 2,
+			LengthHeader::U32(_) => 4,
+			LengthHeader::U64(_) => 8,
+			LengthHeader::Varint => match 64 - value.leading_zeros() as usize {
+				0 => 1,
+				bits => bits.div_ceil(7)
+			}
+		}
+	}
+}
+
+
+/// A codec for frames that are prefixed by their length
+///
+/// The value stored in the header is computed as
+/// `payload_len + length_offset (+ header_len if the length includes the header)`; decoding
+/// performs the inverse computation.
+#[derive(Copy, Clone, Debug)]
+pub struct LengthPrefixedCodec {
+	header: LengthHeader,
+	includes_header: bool,
+	length_offset: i64,
+	max_frame_len: usize
+}
+impl LengthPrefixedCodec {
+	/// Creates a new `LengthPrefixedCodec`
+	///
+	/// Parameters:
+	///  - `header`: The encoding of the length-header
+	///
+	/// Returns __the new `LengthPrefixedCodec`__
+	pub fn new(header: LengthHeader) -> Self {
+		LengthPrefixedCodec{ header, includes_header: false, length_offset: 0, max_frame_len: usize::MAX }
+	}
+	
+	
+	/// The encoding of the length-header
+	///
+	/// Returns __the encoding of the length-header__
+	pub fn header(&self) -> LengthHeader {
+		self.header
+	}
+	
+	
+	/// Sets whether the header value also counts the header itself (the default is `false`)
+	///
+	/// Parameters:
+	///  - `includes_header`: Whether the header value includes the length of the header
+	pub fn set_length_includes_header(&mut self, includes_header: bool) {
+		self.includes_header = includes_header
+	}
+	/// Whether the header value also counts the header itself
+	///
+	/// Returns __`true`__ if the header value includes the length of the header or __`false`__
+	/// otherwise
+	pub fn length_includes_header(&self) -> bool {
+		self.includes_header
+	}
+	
+	
+	/// Sets a constant offset that is added to the payload length to get the header value (the
+	/// default offset is `0`)
+	///
+	/// Parameters:
+	///  - `offset`: The offset to add to the payload length (may be negative)
+	pub fn set_length_offset(&mut self, offset: i64) {
+		self.length_offset = offset
+	}
+	/// The constant offset that is added to the payload length to get the header value
+	///
+	/// Returns __the current length offset__
+	pub fn length_offset(&self) -> i64 {
+		self.length_offset
+	}
+	
+	
+	/// Sets the maximum payload length (the default maximum is `usize::MAX`)
+	///
+	/// _Info: Independent of this setting, a frame is always rejected if it (including its header)
+	/// exceeds the limit of the queue it is decoded from._
+	///
+	/// Parameters:
+	///  - `max_frame_len`: The maximum payload length to accept
+	pub fn set_max_frame_len(&mut self, max_frame_len: usize) {
+		self.max_frame_len = max_frame_len
+	}
+	/// The maximum payload length
+	///
+	/// Returns __the maximum payload length__
+	pub fn max_frame_len(&self) -> usize {
+		self.max_frame_len
+	}
+	
+	
+	/// Computes the header value for a payload of `payload_len` bytes
+	///
+	/// Returns either __`Some(header_value)`__ or __`None`__ if the value cannot be represented
+	fn header_value(&self, payload_len: usize) -> Option<u64> {
+		// Compute the value without header first because the varint-length depends on the value
+		let base = payload_len as i128 + self.length_offset as i128;
+		let value = match self.includes_header {
+			true => {
+				let estimate = base + self.header.len(base.max(0) as u64) as i128;
+				base + self.header.len(estimate.max(0) as u64) as i128
+			},
+			false => base
+		};
+		
+		match value {
+			value if value < 0 || value > self.header.max_value() as i128 => None,
+			value => Some(value as u64)
+		}
+	}
+}
+impl<Q: ReadableSliceQueue<u8> + ?Sized> Decoder<Q> for LengthPrefixedCodec {
+	type Item = Vec<u8>;
+	
+	/// Tries to decode and consume the next frame
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the frame from
+	///
+	/// Returns either __`Ok(Some(payload))`__ if a complete frame was decoded and consumed,
+	/// __`Ok(None)`__ if more data is needed (the queue is left untouched in this case) or
+	/// __`Err(codec_error)`__ if the header is malformed or the frame is too large
+	fn decode(&mut self, queue: &mut Q) -> Result<Option<Vec<u8>>, CodecError> {
+		// Parse the header
+		let (value, header_len) = match self.header.read(queue)? {
+			Some(header) => header,
+			None => return Ok(None)
+		};
+		
+		// Compute the payload length
+		let mut payload_len = value as i128 - self.length_offset as i128;
+		if self.includes_header { payload_len -= header_len as i128 }
+		if payload_len < 0 { return Err(CodecError::Malformed("length field is too small")) }
+		
+		// Validate the payload and frame length
+		let payload_len = min(payload_len, usize::MAX as i128) as usize;
+		if payload_len > self.max_frame_len {
+			return Err(CodecError::TooLarge{ size: payload_len, max: self.max_frame_len })
+		}
+		let frame_len = payload_len.saturating_add(header_len);
+		if frame_len > queue.limit() { return Err(CodecError::TooLarge{ size: frame_len, max: queue.limit() }) }
+		
+		// Consume the frame if it is complete
+		if queue.len() < frame_len { return Ok(None) }
+		queue.drop_n(header_len).expect("Failed to discard buffered header");
+		Ok(Some(queue.pop_n(payload_len).expect("Failed to pop buffered payload")))
+	}
+}
+impl<Q: WriteableSliceQueue<u8> + ?Sized> Encoder<Q, [u8]> for LengthPrefixedCodec {
+	/// Encodes `payload` as frame and appends it at the end of `queue`
+	///
+	/// Parameters:
+	///  - `payload`: The payload to encode
+	///  - `queue`: The queue to push the encoded frame to
+	///
+	/// Returns either __`Ok(())`__ if the entire frame was pushed or __`Err(codec_error)`__ if the
+	/// payload is too large or the queue has not enough space remaining (nothing is pushed in this
+	/// case)
+	fn encode(&mut self, payload: &[u8], queue: &mut Q) -> Result<(), CodecError> {
+		// Validate the payload length
+		if payload.len() > self.max_frame_len {
+			return Err(CodecError::TooLarge{ size: payload.len(), max: self.max_frame_len })
+		}
+		
+		// Compute and encode the header
+		let value = match self.header_value(payload.len()) {
+			Some(value) => value,
+			None => {
+				let max = self.header.max_value() as i128 - self.length_offset as i128
+					- if self.includes_header { self.header.len(self.header.max_value()) as i128 } else { 0 };
+				return Err(CodecError::TooLarge{ size: payload.len(), max: min(max.max(0), usize::MAX as i128) as usize })
+			}
+		};
+		
+		// Push the header and payload if the frame fits completely
+		let needed = self.header.len(value).saturating_add(payload.len());
+		if needed > queue.remaining() { return Err(CodecError::NoSpace{ needed, remaining: queue.remaining() }) }
+		self.header.write(value, queue);
+		queue.push_from(payload).expect("Failed to push payload");
+		Ok(())
+	}
+}
@@ -0,0 +1,149 @@
+//! Streaming codecs that pop complete frames from and push encoded frames to byte queues
+//!
+//! All decoders leave the queue untouched if the buffered data does not yet contain a complete
+//! frame, so they can simply be called again after more data has been pushed.
+
+mod length_prefixed;
+mod cobs;
+mod slip;
+mod http;
+mod resp;
+mod netstring;
+
+pub use self::{
+	length_prefixed::{ LengthPrefixedCodec, LengthHeader, ByteOrder },
+	cobs::CobsCodec, slip::SlipCodec,
+	http::{ HttpHeadDecoder, HttpHead, StartLine, ChunkedDecoder, ChunkedItem },
+	resp::{ RespCodec, RespValue }, netstring::NetstringCodec
+};
+use ReadableSliceQueue;
+use std::{
+	error::Error, ops::{ IndexMut, Range },
+	fmt::{ Display, Formatter, Result as FmtResult }
+};
+
+
+/// An error that occurred during encoding or decoding
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CodecError {
+	/// The frame is larger than the allowed maximum and will thus never fit
+	///
+	/// Fields:
+	///  - `size`: The size of the frame
+	///  - `max`: The maximum allowed size (either a codec setting or the queue's limit)
+	TooLarge{ size: usize, max: usize },
+	/// There is not enough space remaining in the target queue to push the entire encoded frame
+	///
+	/// _Info: Nothing has been pushed if this error is returned_
+	///
+	/// Fields:
+	///  - `needed`: The amount of bytes the encoded frame needs
+	///  - `remaining`: The amount of bytes remaining in the queue
+	NoSpace{ needed: usize, remaining: usize },
+	/// The buffered data is not a valid encoding
+	Malformed(&'static str)
+}
+impl Display for CodecError {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		match self {
+			CodecError::TooLarge{ size, max } => write!(f, "Frame size {} exceeds the maximum of {}", size, max),
+			CodecError::NoSpace{ needed, remaining } =>
+				write!(f, "Frame needs {} bytes but only {} bytes are remaining", needed, remaining),
+			CodecError::Malformed(what) => write!(f, "Malformed input: {}", what)
+		}
+	}
+}
+impl Error for CodecError {}
+
+
+/// A decoder that pops complete items from a queue of type `Q`
+pub trait Decoder<Q: ?Sized> {
+	/// The decoded item type
+	type Item;
+	
+	/// Tries to decode and consume the next item
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the item from
+	///
+	/// Returns either __`Ok(Some(item))`__ if a complete item was decoded and consumed,
+	/// __`Ok(None)`__ if more data is needed (the queue is left untouched in this case) or
+	/// __`Err(codec_error)`__ if the buffered data cannot be decoded
+	fn decode(&mut self, queue: &mut Q) -> Result<Option<Self::Item>, CodecError>;
+}
+
+
+/// An encoder that pushes encoded items of type `I` to a queue of type `Q`
+pub trait Encoder<Q: ?Sized, I: ?Sized> {
+	/// Encodes `item` and appends it at the end of `queue`
+	///
+	/// Parameters:
+	///  - `item`: The item to encode
+	///  - `queue`: The queue to push the encoded item to
+	///
+	/// Returns either __`Ok(())`__ if the entire item was pushed or __`Err(codec_error)`__ if the
+	/// item could not be encoded (nothing is pushed in this case)
+	fn encode(&mut self, item: &I, queue: &mut Q) -> Result<(), CodecError>;
+}
+
+
+
+/// Pops the next `delimiter`-terminated frame from `queue` and unstuffs it in place
+///
+/// Empty frames are skipped. If a frame cannot be unstuffed or is too large, the frame (or if no
+/// delimiter was found, all buffered data) is discarded so that the next call can resynchronize on
+/// the next delimiter.
+///
+/// Parameters:
+///  - `queue`: The queue to pop the frame from
+///  - `delimiter`: The byte that terminates each frame
+///  - `max_frame_len`: The maximum length of an unstuffed frame
+///  - `max_encoded_len`: The maximum length of a stuffed frame (excluding the delimiter)
+///  - `unstuff`: A function that unstuffs the frame in place and returns the unstuffed length
+///
+/// Returns either __`Ok(Some(frame))`__ if a frame was popped, __`Ok(None)`__ if more data is
+/// needed or __`Err(codec_error)`__ if a frame was discarded
+fn decode_delimited<Q>(queue: &mut Q, delimiter: u8, max_frame_len: usize, max_encoded_len: usize,
+	unstuff: impl Fn(&mut[u8]) -> Result<usize, CodecError>) -> Result<Option<Vec<u8>>, CodecError>
+	where Q: ReadableSliceQueue<u8> + IndexMut<Range<usize>, Output = [u8]> + ?Sized
+{
+	loop {
+		// Find the end of the next frame
+		let buffered = queue.len();
+		let encoded_len = match queue[0..buffered].iter().position(|b| *b == delimiter) {
+			Some(0) => {
+				queue.drop_n(1).expect("Failed to discard empty frame");
+				continue
+			},
+			Some(encoded_len) if encoded_len <= max_encoded_len => encoded_len,
+			Some(encoded_len) => {
+				queue.drop_n(encoded_len + 1).expect("Failed to discard oversized frame");
+				return Err(CodecError::TooLarge{ size: encoded_len, max: max_encoded_len })
+			},
+			None if buffered > max_encoded_len || buffered >= queue.limit() => {
+				let max = if buffered > max_encoded_len { max_encoded_len } else { queue.limit() };
+				queue.drop_n(buffered).expect("Failed to discard oversized frame");
+				return Err(CodecError::TooLarge{ size: buffered, max })
+			},
+			None => return Ok(None)
+		};
+		
+		// Unstuff the frame in place and validate the length
+		let unstuffed_len = match unstuff(&mut queue[0..encoded_len]) {
+			Ok(unstuffed_len) if unstuffed_len > max_frame_len => Err(CodecError::TooLarge{ size: unstuffed_len, max: max_frame_len }),
+			result => result
+		};
+		let unstuffed_len = match unstuffed_len {
+			Ok(unstuffed_len) => unstuffed_len,
+			Err(e) => {
+				queue.drop_n(encoded_len + 1).expect("Failed to discard invalid frame");
+				return Err(e)
+			}
+		};
+		
+		// Pop the frame and discard the remaining stuffed bytes and the delimiter
+		let frame = queue.pop_n(unstuffed_len).expect("Failed to pop unstuffed frame");
+		queue.drop_n(encoded_len + 1 - unstuffed_len).expect("Failed to discard stuffed bytes");
+		return Ok(Some(frame))
+	}
+}
\ No newline at end of file
@@ -0,0 +1,111 @@
+use super::{ CodecError, Decoder, Encoder };
+use ::{ ReadableSliceQueue, WriteableSliceQueue };
+use std::cmp::min;
+
+
+/// A codec for netstrings (`<len>:<data>,`)
+///
+/// Decoding only consumes a netstring once it has been buffered completely; incomplete netstrings
+/// are left in the queue. If a netstring is invalid or too large, the queue is left untouched and
+/// the connection should be closed.
+#[derive(Copy, Clone, Debug)]
+pub struct NetstringCodec {
+	max_len: usize
+}
+impl NetstringCodec {
+	/// Creates a new `NetstringCodec`
+	///
+	/// Returns __the new `NetstringCodec`__
+	pub fn new() -> Self {
+		NetstringCodec{ max_len: usize::MAX }
+	}
+	
+	
+	/// Sets the maximum data length (the default maximum is `usize::MAX`)
+	///
+	/// _Info: Independent of this setting, a netstring is always rejected if it exceeds the limit
+	/// of the queue it is decoded from._
+	///
+	/// Parameters:
+	///  - `max_len`: The maximum data length to accept
+	pub fn set_max_len(&mut self, max_len: usize) {
+		self.max_len = max_len
+	}
+	/// The maximum data length
+	///
+	/// Returns __the maximum data length__
+	pub fn max_len(&self) -> usize {
+		self.max_len
+	}
+}
+impl Default for NetstringCodec {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<Q: ReadableSliceQueue<u8> + ?Sized> Decoder<Q> for NetstringCodec {
+	type Item = Vec<u8>;
+	
+	/// Tries to decode and consume the next netstring
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the netstring from
+	///
+	/// Returns either __`Ok(Some(data))`__ if a complete netstring was decoded and consumed,
+	/// __`Ok(None)`__ if more data is needed (the queue is left untouched in this case) or
+	/// __`Err(codec_error)`__ if the netstring is invalid or too large
+	fn decode(&mut self, queue: &mut Q) -> Result<Option<Vec<u8>>, CodecError> {
+		// Peek at the length prefix (which has at most 20 digits)
+		let prefix = match queue.peek_n(21) { Ok(bytes) | Err(bytes) => bytes };
+		let digits = prefix.iter().take_while(|b| b.is_ascii_digit()).count();
+		match prefix.get(digits) {
+			_ if digits > 20 => return Err(CodecError::Malformed("netstring length is too long")),
+			Some(b':') if digits > 0 => (),
+			Some(_) => return Err(CodecError::Malformed("invalid netstring length")),
+			None => return Ok(None)
+		}
+		if digits > 1 && prefix[0] == b'0' { return Err(CodecError::Malformed("netstring length has leading zeros")) }
+		
+		// Parse and validate the length
+		let len = prefix[..digits].iter().try_fold(0u64, |len, b| len.checked_mul(10)?.checked_add((b - b'0') as u64))
+			.ok_or(CodecError::Malformed("netstring length overflows 64 bits"))?;
+		let max = min(self.max_len, queue.limit().saturating_sub(digits + 2));
+		if len > max as u64 { return Err(CodecError::TooLarge{ size: min(len, usize::MAX as u64) as usize, max }) }
+		
+		// Validate the trailing comma and pop the data
+		let (start, len) = (digits + 1, len as usize);
+		match queue.peek_n(start + len + 1) {
+			Ok(netstring) if netstring[start + len] == b',' => (),
+			Ok(_) => return Err(CodecError::Malformed("netstring does not end with a comma")),
+			Err(_) => return Ok(None)
+		}
+		queue.drop_n(start).expect("Failed to discard netstring length");
+		let data = queue.pop_n(len).expect("Failed to pop netstring data");
+		queue.drop_n(1).expect("Failed to discard netstring comma");
+		Ok(Some(data))
+	}
+}
+impl<Q: WriteableSliceQueue<u8> + ?Sized> Encoder<Q, [u8]> for NetstringCodec {
+	/// Encodes `data` as netstring and appends it at the end of `queue`
+	///
+	/// Parameters:
+	///  - `data`: The data to encode
+	///  - `queue`: The queue to push the netstring to
+	///
+	/// Returns either __`Ok(())`__ if the entire netstring was pushed or __`Err(codec_error)`__ if
+	/// the data is too large or the queue has not enough space remaining (nothing is pushed in this
+	/// case)
+	fn encode(&mut self, data: &[u8], queue: &mut Q) -> Result<(), CodecError> {
+		if data.len() > self.max_len { return Err(CodecError::TooLarge{ size: data.len(), max: self.max_len }) }
+		
+		// Push the netstring if it fits completely
+		let prefix = format!("{}:", data.len());
+		let needed = prefix.len() + data.len() + 1;
+		if needed > queue.remaining() { return Err(CodecError::NoSpace{ needed, remaining: queue.remaining() }) }
+		
+		queue.push_from(prefix.as_bytes()).expect("Failed to push netstring length");
+		queue.push_from(data).expect("Failed to push netstring data");
+		queue.push_from(b",").expect("Failed to push netstring comma");
+		Ok(())
+	}
+}
@@ -0,0 +1,312 @@
+use super::{ CodecError, Decoder, Encoder };
+use ::{ ReadableSliceQueue, WriteableSliceQueue };
+use std::{ cmp::min, str, f64 };
+
+
+/// A RESP2/RESP3 value
+#[derive(Clone, Debug, PartialEq)]
+pub enum RespValue {
+	/// A simple string (`+`)
+	SimpleString(Vec<u8>),
+	/// A simple error (`-`)
+	Error(Vec<u8>),
+	/// An integer (`:`)
+	Integer(i64),
+	/// A bulk string (`$`); `None` is the RESP2 null bulk string (`$-1`)
+	BulkString(Option<Vec<u8>>),
+	/// An array (`*`); `None` is the RESP2 null array (`*-1`)
+	Array(Option<Vec<RespValue>>),
+	/// The RESP3 null value (`_`)
+	Null,
+	/// A RESP3 boolean (`#`)
+	Boolean(bool),
+	/// A RESP3 double (`,`)
+	Double(f64),
+	/// A RESP3 big number (`(`) as decimal string
+	BigNumber(Vec<u8>),
+	/// A RESP3 bulk error (`!`)
+	BulkError(Vec<u8>),
+	/// A RESP3 verbatim string (`=`) with its three-byte format (e.g. `txt`)
+	VerbatimString([u8; 3], Vec<u8>),
+	/// A RESP3 map (`%`)
+	Map(Vec<(RespValue, RespValue)>),
+	/// A RESP3 set (`~`)
+	Set(Vec<RespValue>),
+	/// A RESP3 push (`>`)
+	Push(Vec<RespValue>)
+}
+
+
+/// Finds the first CRLF in `bytes`
+fn find_crlf(bytes: &[u8]) -> Option<usize> {
+	bytes.windows(2).position(|window| window == b"\r\n")
+}
+/// Parses a signed decimal integer
+fn parse_integer(bytes: &[u8]) -> Result<i64, CodecError> {
+	str::from_utf8(bytes).ok().and_then(|s| s.parse().ok())
+		.ok_or(CodecError::Malformed("invalid RESP integer"))
+}
+
+
+/// A codec for RESP2/RESP3 ("REdis Serialization Protocol") values
+///
+/// Decoding only consumes a value once it has been buffered completely; incomplete values are
+/// left in the queue. If a value is invalid or too large, the queue is left untouched and the
+/// connection should be closed.
+#[derive(Copy, Clone, Debug)]
+pub struct RespCodec {
+	max_bulk_len: usize,
+	max_depth: usize
+}
+impl RespCodec {
+	/// Creates a new `RespCodec`
+	///
+	/// Returns __the new `RespCodec`__
+	pub fn new() -> Self {
+		RespCodec{ max_bulk_len: usize::MAX, max_depth: 32 }
+	}
+	
+	
+	/// Sets the maximum length of a bulk string, bulk error or verbatim string (the default maximum
+	/// is `usize::MAX`)
+	///
+	/// _Info: Independent of this setting, a value is always rejected if it exceeds the limit of
+	/// the queue it is decoded from._
+	///
+	/// Parameters:
+	///  - `max_bulk_len`: The maximum bulk length to accept
+	pub fn set_max_bulk_len(&mut self, max_bulk_len: usize) {
+		self.max_bulk_len = max_bulk_len
+	}
+	/// The maximum length of a bulk string, bulk error or verbatim string
+	///
+	/// Returns __the maximum bulk length__
+	pub fn max_bulk_len(&self) -> usize {
+		self.max_bulk_len
+	}
+	
+	/// Sets the maximum nesting depth of aggregate values (the default maximum is `32`)
+	///
+	/// Parameters:
+	///  - `max_depth`: The maximum nesting depth to accept
+	pub fn set_max_depth(&mut self, max_depth: usize) {
+		self.max_depth = max_depth
+	}
+	/// The maximum nesting depth of aggregate values
+	///
+	/// Returns __the maximum nesting depth__
+	pub fn max_depth(&self) -> usize {
+		self.max_depth
+	}
+	
+	
+	/// Parses the value at the beginning of `bytes`
+	///
+	/// Returns either __`Ok(Some((value, value_len)))`__ if the value is complete, __`Ok(None)`__ if
+	/// more bytes are needed or __`Err(codec_error)`__ if the value is invalid or too large
+	fn parse(&self, bytes: &[u8], depth: usize, limit: usize) -> Result<Option<(RespValue, usize)>, CodecError> {
+		// Read the type and the header line
+		let line_len = match find_crlf(bytes) {
+			Some(line_len) => line_len,
+			None => return Ok(None)
+		};
+		let (line, mut pos) = (&bytes[1..line_len], line_len + 2);
+		if line_len == 0 { return Err(CodecError::Malformed("missing RESP type")) }
+		
+		// Parse simple types
+		let value = match bytes[0] {
+			b'+' => RespValue::SimpleString(line.to_vec()),
+			b'-' => RespValue::Error(line.to_vec()),
+			b':' => RespValue::Integer(parse_integer(line)?),
+			b'(' => RespValue::BigNumber(line.to_vec()),
+			b'_' if line.is_empty() => RespValue::Null,
+			b'#' if line == b"t" => RespValue::Boolean(true),
+			b'#' if line == b"f" => RespValue::Boolean(false),
+			b',' => match line {
+				b"inf" => RespValue::Double(f64::INFINITY),
+				b"-inf" => RespValue::Double(f64::NEG_INFINITY),
+				b"nan" => RespValue::Double(f64::NAN),
+				line => RespValue::Double(str::from_utf8(line).ok().and_then(|s| s.parse().ok())
+					.ok_or(CodecError::Malformed("invalid RESP double"))?)
+			},
+			b'$' | b'!' | b'=' => {
+				// Parse the length and handle the null bulk string
+				let len = parse_integer(line)?;
+				if len == -1 && bytes[0] == b'$' { return Ok(Some((RespValue::BulkString(None), pos))) }
+				if len < 0 { return Err(CodecError::Malformed("negative RESP length")) }
+				
+				// Validate the length and wait until the data is complete
+				let max = min(self.max_bulk_len, limit.saturating_sub(pos + 2));
+				if len as u64 > max as u64 { return Err(CodecError::TooLarge{ size: min(len as u64, usize::MAX as u64) as usize, max }) }
+				let len = len as usize;
+				match bytes.get(pos + len .. pos + len + 2) {
+					Some(b"\r\n") => (),
+					Some(_) => return Err(CodecError::Malformed("RESP bulk data does not end with CRLF")),
+					None => return Ok(None)
+				}
+				
+				// Extract the data
+				let data = bytes[pos .. pos + len].to_vec();
+				pos += len + 2;
+				match bytes[0] {
+					b'$' => RespValue::BulkString(Some(data)),
+					b'!' => RespValue::BulkError(data),
+					_ if data.len() >= 4 && data[3] == b':' => RespValue::VerbatimString([data[0], data[1], data[2]], data[4..].to_vec()),
+					_ => return Err(CodecError::Malformed("invalid RESP verbatim string format"))
+				}
+			},
+			b'*' | b'%' | b'~' | b'>' => {
+				// Parse the count and handle the null array
+				let count = parse_integer(line)?;
+				if count == -1 && bytes[0] == b'*' { return Ok(Some((RespValue::Array(None), pos))) }
+				if count < 0 { return Err(CodecError::Malformed("negative RESP length")) }
+				if depth >= self.max_depth { return Err(CodecError::TooLarge{ size: depth + 1, max: self.max_depth }) }
+				
+				// Parse the elements (a map has two elements per entry)
+				let elements_count = if bytes[0] == b'%' { (count as u64).saturating_mul(2) } else { count as u64 };
+				let mut elements = Vec::with_capacity(min(elements_count, 1024) as usize);
+				for _ in 0..elements_count {
+					match self.parse(&bytes[pos..], depth + 1, limit.saturating_sub(pos))? {
+						Some((element, element_len)) => {
+							elements.push(element);
+							pos += element_len;
+						},
+						None => return Ok(None)
+					}
+				}
+				
+				match bytes[0] {
+					b'*' => RespValue::Array(Some(elements)),
+					b'~' => RespValue::Set(elements),
+					b'>' => RespValue::Push(elements),
+					_ => {
+						let mut entries = Vec::with_capacity(elements.len() / 2);
+						let mut elements = elements.into_iter();
+						while let (Some(key), Some(value)) = (elements.next(), elements.next()) { entries.push((key, value)) }
+						RespValue::Map(entries)
+					}
+				}
+			},
+			_ => return Err(CodecError::Malformed("invalid RESP type"))
+		};
+		Ok(Some((value, pos)))
+	}
+	
+	/// Validates that `value` (and all nested values) can be serialized without breaking the framing
+	///
+	/// Returns either __`Ok(())`__ or __`Err(codec_error)`__ if a simple value contains CR or LF or a
+	/// verbatim format contains CR, LF or `:`
+	fn validate(value: &RespValue) -> Result<(), CodecError> {
+		match value {
+			RespValue::SimpleString(line) | RespValue::Error(line) | RespValue::BigNumber(line)
+				if line.iter().any(|b| *b == b'\r' || *b == b'\n') => Err(CodecError::Malformed("RESP simple value contains CR or LF")),
+			RespValue::VerbatimString(format, _) if format.iter().any(|b| *b == b'\r' || *b == b'\n' || *b == b':') => {
+				Err(CodecError::Malformed("invalid RESP verbatim format"))
+			},
+			RespValue::Array(Some(elements)) | RespValue::Set(elements) | RespValue::Push(elements) => {
+				elements.iter().try_for_each(Self::validate)
+			},
+			RespValue::Map(entries) => entries.iter().try_for_each(|(key, value)| {
+				Self::validate(key)?;
+				Self::validate(value)
+			}),
+			_ => Ok(())
+		}
+	}
+	/// Serializes `value` into `buf`
+	fn serialize(value: &RespValue, buf: &mut Vec<u8>) {
+		macro_rules! simple {
+			($kind:expr, $line:expr) => ({
+				buf.push($kind);
+				buf.extend_from_slice($line);
+				buf.extend_from_slice(b"\r\n");
+			});
+		}
+		macro_rules! bulk {
+			($kind:expr, $($data:expr),+) => ({
+				simple!($kind, (0 $(+ $data.len())+).to_string().as_bytes());
+				$(buf.extend_from_slice($data);)+
+				buf.extend_from_slice(b"\r\n");
+			});
+		}
+		
+		match value {
+			RespValue::SimpleString(string) => simple!(b'+', string),
+			RespValue::Error(error) => simple!(b'-', error),
+			RespValue::Integer(integer) => simple!(b':', integer.to_string().as_bytes()),
+			RespValue::BulkString(None) => simple!(b'$', b"-1"),
+			RespValue::BulkString(Some(data)) => bulk!(b'$', data),
+			RespValue::Array(None) => simple!(b'*', b"-1"),
+			RespValue::Array(Some(elements)) | RespValue::Set(elements) | RespValue::Push(elements) => {
+				let kind = match value { RespValue::Set(_) => b'~', RespValue::Push(_) => b'>', _ => b'*' };
+				simple!(kind, elements.len().to_string().as_bytes());
+				elements.iter().for_each(|element| Self::serialize(element, buf));
+			},
+			RespValue::Null => simple!(b'_', b""),
+			RespValue::Boolean(boolean) => simple!(b'#', if *boolean { b"t" } else { b"f" }),
+			RespValue::Double(double) if double.is_nan() => simple!(b',', b"nan"),
+			RespValue::Double(double) if double.is_infinite() => simple!(b',', if *double > 0.0 { b"inf".as_ref() } else { b"-inf" }),
+			RespValue::Double(double) => simple!(b',', double.to_string().as_bytes()),
+			RespValue::BigNumber(number) => simple!(b'(', number),
+			RespValue::BulkError(error) => bulk!(b'!', error),
+			RespValue::VerbatimString(format, data) => bulk!(b'=', format, b":", data),
+			RespValue::Map(entries) => {
+				simple!(b'%', entries.len().to_string().as_bytes());
+				entries.iter().for_each(|(key, value)| {
+					Self::serialize(key, buf);
+					Self::serialize(value, buf);
+				});
+			}
+		}
+	}
+}
+impl Default for RespCodec {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<Q: ReadableSliceQueue<u8> + ?Sized> Decoder<Q> for RespCodec {
+	type Item = RespValue;
+	
+	/// Tries to decode and consume the next value
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the value from
+	///
+	/// Returns either __`Ok(Some(value))`__ if a complete value was decoded and consumed,
+	/// __`Ok(None)`__ if more data is needed (the queue is left untouched in this case) or
+	/// __`Err(codec_error)`__ if the value is invalid or too large
+	fn decode(&mut self, queue: &mut Q) -> Result<Option<RespValue>, CodecError> {
+		let buffered = match queue.peek_n(queue.len()) { Ok(bytes) | Err(bytes) => bytes };
+		match self.parse(buffered, 0, queue.limit())? {
+			Some((value, value_len)) => {
+				queue.drop_n(value_len).expect("Failed to discard value");
+				Ok(Some(value))
+			},
+			None if buffered.len() >= queue.limit() => Err(CodecError::TooLarge{ size: buffered.len(), max: queue.limit() }),
+			None => Ok(None)
+		}
+	}
+}
+impl<Q: WriteableSliceQueue<u8> + ?Sized> Encoder<Q, RespValue> for RespCodec {
+	/// Encodes `value` and appends it at the end of `queue`
+	///
+	/// Parameters:
+	///  - `value`: The value to encode
+	///  - `queue`: The queue to push the encoded value to
+	///
+	/// Returns either __`Ok(())`__ if the entire value was pushed or __`Err(codec_error)`__ if the
+	/// value cannot be encoded (e.g. a simple string that contains CR or LF) or the queue has not
+	/// enough space remaining (nothing is pushed in this case)
+	fn encode(&mut self, value: &RespValue, queue: &mut Q) -> Result<(), CodecError> {
+		Self::validate(value)?;
+		let mut encoded = Vec::new();
+		Self::serialize(value, &mut encoded);
+		
+		if encoded.len() > queue.remaining() {
+			return Err(CodecError::NoSpace{ needed: encoded.len(), remaining: queue.remaining() })
+		}
+		queue.push_from(&encoded).expect("Failed to push encoded value");
+		Ok(())
+	}
+}
@@ -0,0 +1,126 @@
+use super::{ CodecError, Decoder, Encoder, decode_delimited };
+use ::{ ReadableSliceQueue, WriteableSliceQueue };
+use std::ops::{ IndexMut, Range };
+
+
+/// The frame delimiter
+const END: u8 = 0xc0;
+/// The escape byte
+const ESC: u8 = 0xdb;
+/// An escaped `END`-byte (if preceded by `ESC`)
+const ESC_END: u8 = 0xdc;
+/// An escaped `ESC`-byte (if preceded by `ESC`)
+const ESC_ESC: u8 = 0xdd;
+
+
+/// Unstuffs the SLIP-encoded `frame` in place
+///
+/// Returns either __`Ok(unstuffed_len)`__ or __`Err(codec_error)`__ if `frame` is not a valid SLIP
+/// encoding
+fn unstuff(frame: &mut[u8]) -> Result<usize, CodecError> {
+	let (mut read, mut write) = (0, 0);
+	while read < frame.len() {
+		frame[write] = match frame[read] {
+			ESC => {
+				read += 1;
+				match frame.get(read) {
+					Some(&ESC_END) => END,
+					Some(&ESC_ESC) => ESC,
+					Some(_) => return Err(CodecError::Malformed("invalid SLIP escape sequence")),
+					None => return Err(CodecError::Malformed("truncated SLIP escape sequence"))
+				}
+			},
+			b => b
+		};
+		read += 1;
+		write += 1;
+	}
+	Ok(write)
+}
+
+
+/// A codec for SLIP ("Serial Line Internet Protocol", RFC 1055) frames that are terminated by an
+/// `END` (`0xC0`) byte
+///
+/// Decoding unstuffs the frame in place within the queue so that no intermediate buffer is needed.
+/// Empty frames (i.e. consecutive `END`-bytes) are skipped; if a frame is invalid, it is discarded
+/// so that the next `decode`-call resynchronizes on the next `END`-byte.
+#[derive(Copy, Clone, Debug)]
+pub struct SlipCodec {
+	max_frame_len: usize
+}
+impl SlipCodec {
+	/// Creates a new `SlipCodec`
+	///
+	/// Returns __the new `SlipCodec`__
+	pub fn new() -> Self {
+		SlipCodec{ max_frame_len: usize::MAX }
+	}
+	
+	
+	/// Sets the maximum unstuffed frame length (the default maximum is `usize::MAX`)
+	///
+	/// Parameters:
+	///  - `max_frame_len`: The maximum unstuffed frame length to accept
+	pub fn set_max_frame_len(&mut self, max_frame_len: usize) {
+		self.max_frame_len = max_frame_len
+	}
+	/// The maximum unstuffed frame length
+	///
+	/// Returns __the maximum unstuffed frame length__
+	pub fn max_frame_len(&self) -> usize {
+		self.max_frame_len
+	}
+}
+impl Default for SlipCodec {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<Q> Decoder<Q> for SlipCodec where Q: ReadableSliceQueue<u8> + IndexMut<Range<usize>, Output = [u8]> + ?Sized {
+	type Item = Vec<u8>;
+	
+	/// Tries to decode and consume the next frame
+	///
+	/// Parameters:
+	///  - `queue`: The queue to decode the frame from
+	///
+	/// Returns either __`Ok(Some(frame))`__ if a complete frame was decoded and consumed,
+	/// __`Ok(None)`__ if more data is needed (the queue is left untouched in this case) or
+	/// __`Err(codec_error)`__ if an invalid or oversized frame was discarded
+	fn decode(&mut self, queue: &mut Q) -> Result<Option<Vec<u8>>, CodecError> {
+		decode_delimited(queue, END, self.max_frame_len, self.max_frame_len.saturating_mul(2), unstuff)
+	}
+}
+impl<Q: WriteableSliceQueue<u8> + ?Sized> Encoder<Q, [u8]> for SlipCodec {
+	/// Encodes `frame` and appends it together with the `END`-byte at the end of `queue`
+	///
+	/// Parameters:
+	///  - `frame`: The frame to encode
+	///  - `queue`: The queue to push the encoded frame to
+	///
+	/// Returns either __`Ok(())`__ if the entire frame was pushed or __`Err(codec_error)`__ if the
+	/// frame is too large or the queue has not enough space remaining (nothing is pushed in this
+	/// case)
+	fn encode(&mut self, frame: &[u8], queue: &mut Q) -> Result<(), CodecError> {
+		if frame.len() > self.max_frame_len {
+			return Err(CodecError::TooLarge{ size: frame.len(), max: self.max_frame_len })
+		}
+		
+		// Escape the frame
+		let mut stuffed = Vec::with_capacity(frame.len() + 1);
+		frame.iter().for_each(|b| match *b {
+			END => stuffed.extend_from_slice(&[ESC, ESC_END]),
+			ESC => stuffed.extend_from_slice(&[ESC, ESC_ESC]),
+			b => stuffed.push(b)
+		});
+		stuffed.push(END);
+		
+		// Push the stuffed frame if it fits completely
+		if stuffed.len() > queue.remaining() {
+			return Err(CodecError::NoSpace{ needed: stuffed.len(), remaining: queue.remaining() })
+		}
+		queue.push_from(&stuffed).expect("Failed to push stuffed frame");
+		Ok(())
+	}
+}
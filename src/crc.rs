@@ -0,0 +1,60 @@
+/// The lookup table for the CRC-32 (IEEE 802.3) polynomial
+const TABLE: [u32; 256] = table();
+
+/// Computes the lookup table for the reflected polynomial `0xedb88320`
+const fn table() -> [u32; 256] {
+	let (mut table, mut i) = ([0u32; 256], 0);
+	while i < 256 {
+		let (mut crc, mut bit) = (i as u32, 0);
+		while bit < 8 {
+			crc = if crc & 1 == 1 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+			bit += 1;
+		}
+		table[i] = crc;
+		i += 1;
+	}
+	table
+}
+
+
+/// An incremental CRC-32 (IEEE 802.3) checksum
+#[derive(Copy, Clone, Debug)]
+pub struct Crc32(u32);
+impl Crc32 {
+	/// Creates a new checksum
+	pub fn new() -> Self {
+		Crc32(0xffff_ffff)
+	}
+	/// Feeds `bytes` into the checksum
+	pub fn update(&mut self, bytes: &[u8]) -> &mut Self {
+		self.0 = bytes.iter().fold(self.0, |crc, byte| TABLE[((crc ^ *byte as u32) & 0xff) as usize] ^ (crc >> 8));
+		self
+	}
+	/// The checksum over all bytes fed so far
+	pub fn finish(&self) -> u32 {
+		!self.0
+	}
+	
+	/// Computes the checksum over `bytes`
+	pub fn checksum(bytes: &[u8]) -> u32 {
+		Self::new().update(bytes).finish()
+	}
+}
+impl Default for Crc32 {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::Crc32;
+	
+	#[test]
+	fn test_crc32() {
+		assert_eq!(Crc32::checksum(b""), 0);
+		assert_eq!(Crc32::checksum(b"123456789"), 0xcbf4_3926);
+		assert_eq!(Crc32::new().update(b"1234").update(b"56789").finish(), 0xcbf4_3926);
+	}
+}
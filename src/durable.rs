@@ -0,0 +1,504 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue, crc::Crc32 };
+use std::{
+	cmp::min, fs::{ self, File, OpenOptions }, path::{ Path, PathBuf },
+	io::{ self, Read, Write, Seek, SeekFrom, BufReader, Result as IoResult }
+};
+
+
+/// The magic bytes at the beginning of a log
+const MAGIC: &[u8; 4] = b"SLQW";
+/// The current log format version
+const VERSION: u8 = 1;
+/// The length of the log header
+const LOG_HEADER_LEN: u64 = 4 + 1;
+/// The length of a record header (`kind: u8` and `len: u64`)
+const RECORD_HEADER_LEN: usize = 1 + 8;
+/// The record kind for pushed bytes
+const RECORD_PUSH: u8 = 1;
+/// The record kind for consume markers
+const RECORD_CONSUME: u8 = 2;
+
+
+/// A parsed record (`kind`, `len`, `payload` and the length of the serialized record)
+type Record = (u8, usize, Vec<u8>, u64);
+
+
+/// Creates an `InvalidData`-error with `message`
+fn invalid_data(message: &'static str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message)
+}
+/// Serializes a record
+///
+/// The record format (all integers are little endian):
+///  - `kind: u8`: The record kind (`1`: push, `2`: consume)
+///  - `len: u64`: The amount of pushed or consumed bytes
+///  - `payload`: The pushed bytes (only for push records)
+///  - `checksum: u32`: The CRC-32 of the previous record fields
+fn record(kind: u8, len: usize, payload: &[u8]) -> Vec<u8> {
+	let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload.len() + 4);
+	record.push(kind);
+	record.extend_from_slice(&(len as u64).to_le_bytes());
+	record.extend_from_slice(payload);
+	
+	let checksum = Crc32::checksum(&record);
+	record.extend_from_slice(&checksum.to_le_bytes());
+	record
+}
+/// Reads the next record from `reader`
+///
+/// A record that extends to the end of the log but is incomplete or fails its checksum is a torn
+/// write (i.e. the end of the valid log); a corrupted record that is followed by more bytes is an
+/// error.
+///
+/// Returns either __`Ok(Some((kind, len, payload, record_len)))`__, __`Ok(None)`__ if the end of the
+/// valid log has been reached or the I/O error that occurred (`InvalidData` if a record in the
+/// middle of the log is corrupted)
+fn read_record<R: Read>(reader: &mut R, available: u64) -> IoResult<Option<Record>> {
+	// Read the record header
+	let mut header = [0; RECORD_HEADER_LEN];
+	match reader.read_exact(&mut header) {
+		Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+		result => result?
+	}
+	let (kind, len) = (header[0], u64::from_le_bytes([
+		header[1], header[2], header[3], header[4], header[5], header[6], header[7], header[8]
+	]));
+	
+	// Validate the header fields before allocating the payload
+	let payload_len = match kind {
+		RECORD_PUSH => len,
+		RECORD_CONSUME => 0,
+		_ => return Err(invalid_data("Invalid record kind"))
+	};
+	let record_len = match (RECORD_HEADER_LEN as u64 + 4).checked_add(payload_len) {
+		Some(record_len) if record_len <= available => record_len,
+		_ => return Ok(None)
+	};
+	
+	// Read the payload and the checksum
+	let mut payload = vec![0; payload_len as usize];
+	let mut checksum = [0; 4];
+	match reader.read_exact(&mut payload).and_then(|_| reader.read_exact(&mut checksum)) {
+		Err(ref e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+		result => result?
+	}
+	if Crc32::new().update(&header).update(&payload).finish() != u32::from_le_bytes(checksum) {
+		if record_len < available { return Err(invalid_data("Corrupted record in the middle of the log")) }
+		return Ok(None)
+	}
+	Ok(Some((kind, len as usize, payload, record_len)))
+}
+
+
+/// A durable byte queue that appends every change to a write-ahead log
+///
+/// Every `push*`-call appends the pushed bytes to the log and every `pop*`/`drop_n`-call appends a
+/// consume marker, so that `recover` can rebuild exactly the unconsumed bytes after a crash. The
+/// log is compacted (i.e. rewritten to only contain the unconsumed bytes) once it exceeds the
+/// compaction threshold and consists mostly of consumed bytes.
+///
+/// _Info: The log is written with unbuffered writes, but it is only guaranteed to be durable after
+/// `sync` has been called. The limit is not stored in the log._
+///
+/// _Info: If a change cannot be logged, it is not applied (i.e. the `push*`-call returns `Err` as
+/// if the limit had been reached and the consuming call returns `Err` as if the queue was empty)
+/// and the I/O error is reported by the next `sync`-call._
+#[derive(Debug)]
+pub struct DurableQueue {
+	queue: SliceQueue<u8>,
+	log: File,
+	path: PathBuf,
+	log_len: u64,
+	truncated_len: u64,
+	compaction_threshold: u64,
+	error: Option<io::Error>
+}
+impl DurableQueue {
+	/// Creates a new `DurableQueue` with an empty log at `path`
+	///
+	/// Parameters:
+	///  - `path`: The path of the log file (which must not exist yet)
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error that occurred while creating the log
+	pub fn create(path: impl Into<PathBuf>) -> IoResult<Self> {
+		let path = path.into();
+		let mut log = OpenOptions::new().read(true).write(true).create_new(true).open(&path)?;
+		log.write_all(MAGIC)?;
+		log.write_all(&[VERSION])?;
+		Ok(Self::with_log(SliceQueue::new(), log, path, LOG_HEADER_LEN))
+	}
+	/// Rebuilds a `DurableQueue` from the log at `path`
+	///
+	/// _Info: An incomplete or corrupted last record (i.e. a torn write during a crash) marks the
+	/// end of the log; it is truncated before new records are appended and the amount of truncated
+	/// bytes is reported by `truncated_len`._
+	///
+	/// Parameters:
+	///  - `path`: The path of the log file
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error (`InvalidData` if the log header is invalid,
+	/// if a record in the middle of the log is corrupted or if a consume marker consumes more bytes
+	/// than available; the log is not modified in this case)
+	pub fn recover(path: impl Into<PathBuf>) -> IoResult<Self> {
+		let path = path.into();
+		let mut log = OpenOptions::new().read(true).write(true).open(&path)?;
+		let file_len = log.metadata()?.len();
+		
+		// Validate the log header
+		let mut reader = BufReader::new(&mut log);
+		let mut header = [0; LOG_HEADER_LEN as usize];
+		reader.read_exact(&mut header)?;
+		if &header[..4] != MAGIC { return Err(invalid_data("Invalid magic bytes")) }
+		if header[4] != VERSION { return Err(invalid_data("Unsupported log version")) }
+		
+		// Replay the records
+		let (mut queue, mut log_len) = (SliceQueue::new(), LOG_HEADER_LEN);
+		while let Some((kind, len, payload, record_len)) = read_record(&mut reader, file_len - log_len)? {
+			match kind {
+				RECORD_PUSH => queue.push_n(payload).unwrap_or_else(|_| unreachable!("The queue is unlimited")),
+				_ => queue.drop_n(len).map_err(|_| invalid_data("The consume marker exceeds the queue"))?
+			}
+			log_len += record_len;
+		}
+		drop(reader);
+		
+		// Truncate the torn tail
+		if log_len < file_len { log.set_len(log_len)?; }
+		let mut recovered = Self::with_log(queue, log, path, log_len);
+		recovered.truncated_len = file_len - log_len;
+		Ok(recovered)
+	}
+	/// Creates a new `DurableQueue` over an opened log
+	fn with_log(queue: SliceQueue<u8>, log: File, path: PathBuf, log_len: u64) -> Self {
+		DurableQueue{ queue, log, path, log_len, truncated_len: 0, compaction_threshold: 1024 * 1024, error: None }
+	}
+	
+	
+	/// The path of the log file
+	///
+	/// Returns __the path of the log file__
+	pub fn path(&self) -> &Path {
+		&self.path
+	}
+	/// The current length of the log file
+	///
+	/// Returns __the length of the log file in bytes__
+	pub fn log_len(&self) -> u64 {
+		self.log_len
+	}
+	/// The amount of bytes that `recover` has truncated from the end of the log (i.e. the length of
+	/// the torn tail)
+	///
+	/// Returns __the amount of truncated bytes__ (which is `0` if the log was complete or if
+	/// `self` was created using `create`)
+	pub fn truncated_len(&self) -> u64 {
+		self.truncated_len
+	}
+	/// Sets a new compaction threshold (the default threshold is 1 MiB)
+	///
+	/// _Info: The log is compacted automatically after a consuming call if it is larger than the
+	/// threshold and less than half of it is needed to recover the unconsumed bytes. If the automatic
+	/// compaction fails, the error is returned by the next `sync`-call._
+	///
+	/// Parameters:
+	///  - `threshold`: The log length in bytes above which the log may be compacted
+	pub fn set_compaction_threshold(&mut self, threshold: u64) {
+		self.compaction_threshold = threshold
+	}
+	/// The current compaction threshold
+	///
+	/// Returns __the log length in bytes above which the log may be compacted__
+	pub fn compaction_threshold(&self) -> u64 {
+		self.compaction_threshold
+	}
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of bytes that can be stored by `self`
+	pub fn set_limit(&mut self, limit: usize) {
+		self.queue.set_limit(limit)
+	}
+	
+	
+	/// Flushes the log to the disk (i.e. all previous calls are durable after this call succeeds)
+	///
+	/// Returns either __`Ok(())`__ or the I/O error that occurred (if a change could not be logged
+	/// or an automatic compaction has failed since the last call, the log is flushed and this error
+	/// is returned)
+	pub fn sync(&mut self) -> IoResult<()> {
+		self.log.sync_data()?;
+		match self.error.take() {
+			Some(e) => Err(e),
+			None => Ok(())
+		}
+	}
+	/// Rewrites the log so that it only contains the unconsumed bytes
+	///
+	/// _Info: The new log is written to a temporary file next to the log and then renamed over the
+	/// old log, so a crash during the compaction leaves either the old or the new log. The new log
+	/// is synced before the rename._
+	///
+	/// Returns either __`Ok(())`__ or the I/O error that occurred (the old log is kept in this
+	/// case)
+	pub fn compact(&mut self) -> IoResult<()> {
+		let mut compact_path = self.path.clone().into_os_string();
+		compact_path.push(".compact");
+		let compact_path = PathBuf::from(compact_path);
+		let _ = fs::remove_file(&compact_path);
+		
+		// Write the compacted log and replace the old log
+		let result = Self::create(&compact_path).and_then(|mut compacted| {
+			compacted.log_push(&self.queue[..])?;
+			compacted.sync()?;
+			fs::rename(&compact_path, &self.path)?;
+			Ok(compacted)
+		});
+		let compacted = match result {
+			Ok(compacted) => compacted,
+			Err(e) => {
+				let _ = fs::remove_file(&compact_path);
+				return Err(e)
+			}
+		};
+		
+		// Sync the directory so that the rename is durable (this is not supported on every platform)
+		if let Some(dir) = self.path.parent() {
+			let _ = File::open(dir).and_then(|dir| dir.sync_all());
+		}
+		self.log = compacted.log;
+		self.log_len = compacted.log_len;
+		Ok(())
+	}
+	
+	
+	/// Logs the bytes in `src` and appends them at the end (up to the limit)
+	///
+	/// Returns either __`Ok(appended_count)`__ or the I/O error that occurred while logging the
+	/// bytes (nothing is appended in this case)
+	fn log_push(&mut self, src: &[u8]) -> IoResult<usize> {
+		let src = &src[..min(src.len(), self.queue.remaining())];
+		if !src.is_empty() {
+			self.append(&record(RECORD_PUSH, src.len(), src))?;
+			self.queue.push_from(src).unwrap_or_else(|_| unreachable!("The bytes fit into the queue"));
+		}
+		Ok(src.len())
+	}
+	/// Logs a consume marker for up to `n` bytes and calls `consume_fn` to consume them
+	///
+	/// Returns either __`Ok(result)`__ with the result of `consume_fn` or the I/O error that
+	/// occurred while logging the consume marker (nothing is consumed in this case)
+	fn consume<R>(&mut self, n: usize, consume_fn: impl FnOnce(&mut SliceQueue<u8>) -> R) -> IoResult<R> {
+		let n = min(n, self.queue.len());
+		self.log_consume(n)?;
+		let result = consume_fn(&mut self.queue);
+		
+		self.compact_if_necessary();
+		Ok(result)
+	}
+	/// Appends `record` to the log
+	fn append(&mut self, record: &[u8]) -> IoResult<()> {
+		let result = self.log.seek(SeekFrom::Start(self.log_len)).and_then(|_| self.log.write_all(record));
+		match result {
+			Ok(_) => {
+				self.log_len += record.len() as u64;
+				Ok(())
+			},
+			Err(e) => {
+				// Remove the partially written record (if this fails, `recover` ignores the record)
+				let _ = self.log.set_len(self.log_len);
+				Err(e)
+			}
+		}
+	}
+	/// Logs a consume marker for `n` bytes
+	fn log_consume(&mut self, n: usize) -> IoResult<()> {
+		match n {
+			0 => Ok(()),
+			n => self.append(&record(RECORD_CONSUME, n, &[]))
+		}
+	}
+	/// Compacts the log if it is larger than the threshold and consists mostly of consumed bytes
+	///
+	/// _Info: The consuming call has already been logged, so the error of a failed compaction is
+	/// stored until the next `sync`-call and the compaction is retried during the next consuming
+	/// call._
+	fn compact_if_necessary(&mut self) {
+		let needed = LOG_HEADER_LEN + (RECORD_HEADER_LEN + 4 + self.queue.len()) as u64;
+		if self.log_len > self.compaction_threshold && self.log_len > 2 * needed {
+			if let Err(e) = self.compact() { self.error = Some(e) }
+		}
+	}
+}
+impl ReadableSliceQueue<u8> for DurableQueue {
+	fn len(&self) -> usize {
+		self.queue.len()
+	}
+	fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+	fn limit(&self) -> usize {
+		self.queue.limit()
+	}
+	
+	fn peek(&self) -> Option<&u8> {
+		self.queue.peek()
+	}
+	fn peek_n(&self, n: usize) -> Result<&[u8], &[u8]> {
+		self.queue.peek_n(n)
+	}
+	
+	/// Consumes the first byte, logs a consume marker and returns the byte
+	///
+	/// Returns either __`Ok(byte)`__ if there was a byte to consume or __`Err(())`__ if there was
+	/// no byte or if the consume marker could not be logged (the I/O error is reported by the next
+	/// `sync`-call)
+	fn pop(&mut self) -> Result<u8, ()> {
+		match self.consume(1, |queue| queue.pop()) {
+			Ok(result) => result,
+			Err(e) => {
+				self.error = Some(e);
+				Err(())
+			}
+		}
+	}
+	/// Consumes the first `n` bytes, logs a consume marker and returns the bytes
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to consume
+	///
+	/// Returns either __`Ok(bytes)`__ if there were `n` bytes avaliable to consume or
+	/// __`Err(bytes)`__ if less bytes were available (or __`Err([])`__ if the consume marker could
+	/// not be logged; the I/O error is reported by the next `sync`-call)
+	fn pop_n(&mut self, n: usize) -> Result<Vec<u8>, Vec<u8>> {
+		match self.consume(n, |queue| queue.pop_n(n)) {
+			Ok(result) => result,
+			Err(e) => {
+				self.error = Some(e);
+				Err(Vec::new())
+			}
+		}
+	}
+	/// Consumes the first `dst.len()` bytes, logs a consume marker and moves the bytes into `dst`
+	///
+	/// Parameters:
+	///  - `dst`: The target to move the bytes into
+	///
+	/// Returns either __`Ok(())`__ if `dst` was filled completely or __`Err(byte_count)`__ if
+	/// only `byte_count` bytes were moved (or __`Err(0)`__ if the consume marker could not be
+	/// logged; the I/O error is reported by the next `sync`-call)
+	fn pop_into(&mut self, dst: &mut[u8]) -> Result<(), usize> {
+		match self.consume(dst.len(), |queue| queue.pop_into(dst)) {
+			Ok(result) => result,
+			Err(e) => {
+				self.error = Some(e);
+				Err(0)
+			}
+		}
+	}
+	
+	/// Discards the first `n` bytes and logs a consume marker
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to discard
+	///
+	/// Returns either __`Ok(())`__ if `n` bytes were discarded or __`Err(byte_count)`__ if only
+	/// `byte_count` bytes were discarded (or __`Err(0)`__ if the consume marker could not be
+	/// logged; the I/O error is reported by the next `sync`-call)
+	fn drop_n(&mut self, n: usize) -> Result<(), usize> {
+		match self.consume(n, |queue| queue.drop_n(n)) {
+			Ok(result) => result,
+			Err(e) => {
+				self.error = Some(e);
+				Err(0)
+			}
+		}
+	}
+}
+impl WriteableSliceQueue<u8> for DurableQueue {
+	fn remaining(&self) -> usize {
+		self.queue.remaining()
+	}
+	
+	fn reserve_n(&mut self, n: usize) -> Result<(), usize> {
+		self.queue.reserve_n(n)
+	}
+	fn reserved(&self) -> usize {
+		self.queue.reserved()
+	}
+	
+	fn push(&mut self, element: u8) -> Result<(), u8> {
+		self.push_from(&[element]).map_err(|_| element)
+	}
+	fn push_n(&mut self, mut n: Vec<u8>) -> Result<(), Vec<u8>> {
+		match self.push_from(&n) {
+			Ok(()) => Ok(()),
+			Err(appended) => Err(n.split_off(appended))
+		}
+	}
+	/// Logs the bytes in `src` and appends them at the end
+	///
+	/// Parameters:
+	///  - `src`: A slice containing the bytes to append
+	///
+	/// Returns either __`Ok(())`__ if `src` was appended completely or __`Err(appended_count)`__
+	/// if `src` was only appended partially because `self.limit` would have been exceeded (or
+	/// __`Err(0)`__ if the bytes could not be logged; the I/O error is reported by the next
+	/// `sync`-call)
+	fn push_from(&mut self, src: &[u8]) -> Result<(), usize> {
+		match self.log_push(src) {
+			Ok(appended) if appended == src.len() => Ok(()),
+			Ok(appended) => Err(appended),
+			Err(e) => {
+				self.error = Some(e);
+				Err(0)
+			}
+		}
+	}
+	/// Calls `push_fn` to push up to `n` bytes in place and logs them
+	///
+	/// _Info: The bytes are written to a temporary buffer and logged afterwards; if they cannot be
+	/// logged, nothing is pushed and the I/O error is reported by the next `sync`-call._
+	///
+	/// __Warning: This function panics if `self.limit` is exceeded__
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to reserve
+	///  - `push_fn`: The pushing callback
+	///
+	/// Returns either _the amount of bytes pushed_ or _the error `push_fn` returned_
+	fn push_in_place<E>(&mut self, n: usize, mut push_fn: impl FnMut(&mut[u8]) -> Result<usize, E>) -> Result<usize, E> {
+		assert!(self.remaining() >= n, "`self.len() + n` is larger than `self.limit`");
+		let mut buf = vec![0; n];
+		let pushed = push_fn(&mut buf)?;
+		assert!(pushed <= n, "`push_fn` must not claim that it pushed more elements than `n`");
+		Ok(self.push_from(&buf[..pushed]).map(|_| pushed).unwrap_or_else(|appended| appended))
+	}
+}
+impl Read for DurableQueue {
+	/// Pull some bytes from this source into the specified buffer, returning how many bytes were
+	/// read.
+	///
+	/// __This call only fails if the consume marker cannot be logged__
+	fn read(&mut self, buf: &mut[u8]) -> IoResult<usize> {
+		let n = min(buf.len(), self.queue.len());
+		self.consume(n, |queue| queue.pop_into(&mut buf[..n]).unwrap_or_else(|_| unreachable!("The bytes are buffered")))?;
+		Ok(n)
+	}
+}
+impl Write for DurableQueue {
+	/// Write a buffer into this object, returning how many bytes were written.
+	///
+	/// __This call only fails if the bytes cannot be logged__
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		self.log_push(buf)
+	}
+	/// Flushes the log to the disk and reports the I/O error that occurred since the last
+	/// `sync`-call (see `sync`)
+	fn flush(&mut self) -> IoResult<()> {
+		self.sync()
+	}
+}
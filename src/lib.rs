@@ -8,10 +8,94 @@
 //!  - access the underlying buffer directly by using (range-)indices
 //!  - dereference the `SliceQueue<T>` by propagating the `deref()`-call to the underlying `Vec<T>`
 //!  - access it using the `io::Read` and `io::Write` traits
+//!  - address elements by their absolute offset within the stream (which is not affected by
+//!    consuming elements)
+//!  - read and write big/little endian integers, floats and LEB128 varints using the `ReadableByteQueue` and
+//!    `WriteableByteQueue` extension traits
+//!  - push, peek and pop plain-old-data records and reinterpret a `SliceQueue<T>` as `SliceQueue<U>`
+//!    (requires the `unsafe_fast_code` feature)
+//!  - wipe consumed and freed memory using the zeroize mode and wipe the allocations on drop using
+//!    `ZeroizingSliceQueue` (e.g. for key material; requires the `unsafe_fast_code` feature)
+//!  - save a snapshot of a `SliceQueue<u8>` (or a queue of plain-old-data elements) and restore
+//!    it later
+//!  - keep a bounded history of consumed elements and append back-references to it (e.g. for
+//!    LZ77-style decompressors)
+//!  - spill the data beyond a memory threshold into a temporary file using `SpillQueue`
+//!  - log every change to a write-ahead log and recover the unconsumed data after a crash using
+//!    `DurableQueue`
+//!  - exchange bytes between two processes through a shared-memory ring using `ShmQueue` (Linux
+//!    only)
+//!  - share a queue between threads using `SharedSliceQueue<T>` and register its pollable
+//!    readiness handle with epoll-based event loops (Linux only)
+//!  - move whole batches between multiple producers and consumers using the (blocking)
+//!    `push_batch`/`pop_batch`-calls of `SharedSliceQueue<T>`
+//!  - wait until any of several shared queues is readable or writeable using `select`
+//!  - fan a stream out to several subscribers with their own read positions using
+//!    `BroadcastSliceQueue<T>`
+//!  - let urgent elements overtake queued elements using the priority lanes of
+//!    `PrioritySliceQueue<T>`
+//!  - discard elements after a time-to-live using `ExpiringSliceQueue<T>` (with an injectable
+//!    clock)
+//!  - keep sent data until it is acknowledged using `SendBuffer`
+//!  - reassemble segments that arrive out of order using `ReassemblyQueue`
+//!  - pop and peek UTF-8 strings and chars without splitting code points and use `write!` or
+//!    `push_fmt` to append formatted text to a `SliceQueue<u8>`
+//!  - push and pop an arbitrary amount of bits using `BitQueue`
+//!  - preserve message boundaries using `MessageQueue<T>`
+//!  - decode and encode common wire formats (e.g. length-prefixed frames) using the `codec` module
+//!
+//! `SpillQueue` and `DurableQueue` are separate types (instead of modes of `SliceQueue<u8>`)
+//! because their files can fail: they implement `ReadableSliceQueue<u8>` and
+//! `WriteableSliceQueue<u8>`, but a call that fails returns `Err` like a full (or empty) queue and
+//! the I/O error is reported by a separate `flush`/`sync`-call.
 
 mod mem;
 mod queue;
 mod traits;
+mod message_queue;
+mod bytes;
+mod bit_queue;
+mod text;
+mod send_buffer;
+mod reassembly;
+mod spill;
+mod crc;
+mod snapshot;
+mod durable;
+#[cfg(target_os = "linux")]
+mod shm;
+mod shared;
+mod select;
+mod broadcast;
+mod priority;
+mod ttl;
+#[cfg(target_os = "linux")]
+mod readiness;
+#[cfg(feature = "unsafe_fast_code")]
+mod pod;
+#[cfg(feature = "unsafe_fast_code")]
+mod zeroizing;
+pub mod codec;
 
 pub use queue::{ SliceQueue, AutoShrinkMode };
-pub use traits::{ ReadableSliceQueue, WriteableSliceQueue };
\ No newline at end of file
+pub use traits::{ ReadableSliceQueue, WriteableSliceQueue };
+pub use message_queue::MessageQueue;
+pub use bytes::{ ReadableByteQueue, WriteableByteQueue, Incomplete, VarintError };
+pub use bit_queue::{ BitQueue, BitOrder };
+pub use send_buffer::SendBuffer;
+pub use reassembly::ReassemblyQueue;
+pub use spill::SpillQueue;
+pub use durable::DurableQueue;
+#[cfg(target_os = "linux")]
+pub use shm::ShmQueue;
+pub use shared::SharedSliceQueue;
+pub use select::{ select, Interest, Selectable };
+pub use broadcast::{ BroadcastSliceQueue, BroadcastReader, SubscriberId, OverflowPolicy };
+pub use priority::{ PrioritySliceQueue, Scheduling };
+pub use ttl::{ ExpiringSliceQueue, Clock, SystemClock };
+#[cfg(target_os = "linux")]
+pub use readiness::Readiness;
+#[cfg(feature = "unsafe_fast_code")]
+pub use pod::{ Pod, PodError };
+#[cfg(feature = "unsafe_fast_code")]
+pub use zeroizing::ZeroizingSliceQueue;
\ No newline at end of file
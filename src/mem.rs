@@ -1,14 +1,64 @@
 
 #[cfg(feature = "unsafe_fast_code")]
-pub use self::usafe::{ drop_n, drain_n, drain_into };
+pub use self::usafe::{ drop_n, drain_n, drain_into, reinterpret_vec, copy_vec, wipe_spare, realloc_wiping };
 
 #[cfg(not(feature = "unsafe_fast_code"))]
-pub use self::safe::{ drop_n, drain_n, drain_into };
+pub use self::safe::{ drop_n, drain_n, drain_into, wipe_spare, realloc_wiping };
+
+
+/// Reserves capacity for at least `additional` more elements in `vec`
+///
+/// Parameters:
+///  - `vec`: The vector to reserve the capacity in
+///  - `additional`: The amount of additional elements
+///  - `zeroize`: Whether the old allocation should be wiped if `vec` is reallocated
+pub fn reserve<T>(vec: &mut Vec<T>, additional: usize, zeroize: bool) {
+	match zeroize {
+		true if vec.capacity() - vec.len() < additional => {
+			let needed = vec.len().checked_add(additional).expect("capacity overflow");
+			let capacity = ::std::cmp::max(vec.capacity().saturating_mul(2), needed);
+			realloc_wiping(vec, capacity)
+		},
+		true => (),
+		false => vec.reserve(additional)
+	}
+}
+/// Reserves capacity for exactly `additional` more elements in `vec`
+///
+/// Parameters:
+///  - `vec`: The vector to reserve the capacity in
+///  - `additional`: The amount of additional elements
+///  - `zeroize`: Whether the old allocation should be wiped if `vec` is reallocated
+pub fn reserve_exact<T>(vec: &mut Vec<T>, additional: usize, zeroize: bool) {
+	match zeroize {
+		true if vec.capacity() - vec.len() < additional => {
+			let capacity = vec.len().checked_add(additional).expect("capacity overflow");
+			realloc_wiping(vec, capacity)
+		},
+		true => (),
+		false => vec.reserve_exact(additional)
+	}
+}
+/// Shrinks the capacity of `vec` as much as possible
+///
+/// Parameters:
+///  - `vec`: The vector to shrink
+///  - `zeroize`: Whether the old allocation should be wiped if `vec` is reallocated
+pub fn shrink_to_fit<T>(vec: &mut Vec<T>, zeroize: bool) {
+	match zeroize {
+		true if vec.capacity() > vec.len() => {
+			let capacity = vec.len();
+			realloc_wiping(vec, capacity)
+		},
+		true => (),
+		false => vec.shrink_to_fit()
+	}
+}
 
 
 #[cfg(feature = "unsafe_fast_code")]
 mod usafe {
-	use std::{ ptr, mem };
+	use std::{ ptr, mem, sync::atomic::{ self, Ordering } };
 	
 	/// Drops/deallocates all elements in `slice`
 	///
@@ -72,6 +122,80 @@ mod usafe {
 		unsafe{ ptr::copy_nonoverlapping(src.as_ptr(), dst.as_mut_ptr(), dst.len()) }
 		unsafe{ discard_n(src, dst.len()) }
 	}
+	
+	/// Reinterprets `vec` in place as `Vec<U>`
+	///
+	/// __Warning: Every bit pattern of `T` must be a valid `U` and `T` must not contain any padding
+	/// bytes__
+	///
+	/// Returns either __`Ok(reinterpreted)`__ or __`Err(vec)`__ if `T` and `U` have different
+	/// alignments or the length or capacity in bytes are not a multiple of `U`'s size
+	pub unsafe fn reinterpret_vec<T, U>(vec: Vec<T>) -> Result<Vec<U>, Vec<T>> {
+		let (len, capacity) = (vec.len() * mem::size_of::<T>(), vec.capacity() * mem::size_of::<T>());
+		let size = mem::size_of::<U>();
+		if mem::align_of::<T>() != mem::align_of::<U>() || size == 0 || len % size != 0 || capacity % size != 0 {
+			return Err(vec)
+		}
+		
+		// Take ownership over the allocation and reinterpret it
+		let mut vec = mem::ManuallyDrop::new(vec);
+		Ok(Vec::from_raw_parts(vec.as_mut_ptr() as *mut U, len / size, capacity / size))
+	}
+	
+	/// Copies the bytes of `src` into a new `Vec<U>`
+	///
+	/// __Warning: Every bit pattern of `T` must be a valid `U` and `T` must not contain any padding
+	/// bytes__
+	///
+	/// __Warning: Panics if the length of `src` in bytes is not a multiple of `U`'s size__
+	pub unsafe fn copy_vec<T, U>(src: &[T]) -> Vec<U> {
+		let (len, size) = (mem::size_of_val(src), mem::size_of::<U>());
+		assert!(size > 0 && len % size == 0, "The length of `src` is not a multiple of `U`'s size");
+		
+		// Create the new vector and copy the bytes
+		let mut dst = Vec::<U>::with_capacity(len / size);
+		ptr::copy_nonoverlapping(src.as_ptr() as *const u8, dst.as_mut_ptr() as *mut u8, len);
+		dst.set_len(len / size);
+		dst
+	}
+	
+	/// Overwrites the unused slots between `vec.len()` and `until` with zeroes using volatile writes
+	/// that cannot be optimized away
+	///
+	/// __Warning: Panics if `until` is greater than `vec.capacity()`__
+	///
+	/// Parameters:
+	///  - `vec`: The vector whose unused slots should be wiped
+	///  - `until`: The end of the slots to wipe
+	pub fn wipe_spare<T>(vec: &mut Vec<T>, until: usize) {
+		assert!(until <= vec.capacity(), "`until` is greater than the capacity");
+		if until <= vec.len() { return }
+		
+		// Wipe the slots byte by byte
+		let bytes = (until - vec.len()) * mem::size_of::<T>();
+		unsafe {
+			let ptr = vec.as_mut_ptr().add(vec.len()) as *mut u8;
+			(0..bytes).for_each(|i| ptr::write_volatile(ptr.add(i), 0));
+		}
+		atomic::compiler_fence(Ordering::SeqCst)
+	}
+	/// Moves the elements of `vec` into a new allocation with `capacity` and wipes the old
+	/// allocation before it is freed
+	///
+	/// __Warning: Panics if `capacity` is smaller than `vec.len()`__
+	///
+	/// Parameters:
+	///  - `vec`: The vector to reallocate
+	///  - `capacity`: The capacity of the new allocation
+	pub fn realloc_wiping<T>(vec: &mut Vec<T>, capacity: usize) {
+		assert!(capacity >= vec.len(), "`capacity` is smaller than the length");
+		
+		let mut new = Vec::with_capacity(capacity);
+		new.append(vec);
+		let old_capacity = vec.capacity();
+		wipe_spare(vec, old_capacity);
+		*vec = new
+	}
 }
 
 
@@ -89,6 +213,16 @@ mod safe {
 		let (mut src, dst) = (src.drain(..dst.len()), dst.iter_mut());
 		dst.for_each(|t| *t = src.next().unwrap());
 	}
+	
+	// Wiping memory requires unsafe code, so the zeroize mode is not available and these functions
+	// are plain `Vec`-operations
+	pub fn wipe_spare<T>(_vec: &mut Vec<T>, _until: usize) {}
+	
+	pub fn realloc_wiping<T>(vec: &mut Vec<T>, capacity: usize) {
+		let mut new = Vec::with_capacity(capacity);
+		new.append(vec);
+		*vec = new
+	}
 }
 
 
@@ -159,4 +293,42 @@ mod tests {
 		src_base.iter().for_each(|rc| assert_eq!(Rc::strong_count(rc), 2));
 		dst_base.iter().for_each(|rc| assert_eq!(Rc::strong_count(rc), 1));
 	}
+	
+	#[cfg(feature = "unsafe_fast_code")]
+	#[test]
+	fn test_wipe_spare() {
+		use super::{ wipe_spare, realloc_wiping };
+		
+		// Truncate the vector and wipe the vacated slots
+		let mut vec = vec![0xffffu16; 8];
+		vec.truncate(2);
+		wipe_spare(&mut vec, 6);
+		let spare = unsafe{ ::std::slice::from_raw_parts(vec.as_ptr(), 8) };
+		assert_eq!(spare, &[0xffff, 0xffff, 0, 0, 0, 0, 0xffff, 0xffff]);
+		
+		// Reallocate the vector
+		realloc_wiping(&mut vec, 2);
+		assert_eq!(vec, [0xffff, 0xffff]);
+		assert_eq!(vec.capacity(), 2);
+	}
+	
+	#[cfg(feature = "unsafe_fast_code")]
+	#[test]
+	fn test_reinterpret_vec() {
+		use super::{ reinterpret_vec, copy_vec };
+		
+		// Reinterpret `u32`s as `i32`s and back
+		let mut vec = Vec::with_capacity(8);
+		vec.extend_from_slice(&[1u32, 2, 0xffffffff]);
+		let reinterpreted: Vec<i32> = unsafe{ reinterpret_vec(vec) }.unwrap();
+		assert_eq!(reinterpreted, [1, 2, -1]);
+		assert_eq!(reinterpreted.capacity(), 8);
+		
+		// Validate that different alignments are rejected and copy the elements instead
+		let bytes: Vec<u8> = unsafe{ copy_vec(&reinterpreted) };
+		assert_eq!(bytes.len(), 12);
+		let bytes = unsafe{ reinterpret_vec::<u8, u32>(bytes) }.unwrap_err();
+		let copied: Vec<u32> = unsafe{ copy_vec(&bytes) };
+		assert_eq!(copied, [1, 2, 0xffffffff]);
+	}
 }
\ No newline at end of file
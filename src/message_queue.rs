@@ -0,0 +1,210 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{ cmp::min, collections::VecDeque };
+
+
+/// A queue that preserves the boundaries of the messages pushed into it
+///
+/// The payloads are stored contiguously in a `SliceQueue<T>`; the message lengths are stored in a
+/// separate index. Both the amount of elements and the amount of messages can be limited.
+#[derive(Clone, Debug)]
+pub struct MessageQueue<T> {
+	payloads: SliceQueue<T>,
+	lengths: VecDeque<usize>,
+	message_limit: usize
+}
+impl<T> MessageQueue<T> {
+	/// Creates a new `MessageQueue`
+	///
+	/// Returns __the new `MessageQueue`__
+	pub fn new() -> Self {
+		MessageQueue{ payloads: SliceQueue::new(), lengths: VecDeque::new(), message_limit: usize::MAX }
+	}
+	/// Creates a new `MessageQueue` with a predefined element and message limit (the default limits
+	/// are `usize::MAX`)
+	///
+	/// __Warning: Panics if `limit` or `message_limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that can be stored by `self`
+	///  - `message_limit`: The maximum amount of messages that can be stored by `self`
+	///
+	/// Returns __the new `MessageQueue`__
+	pub fn with_limits(limit: usize, message_limit: usize) -> Self {
+		assert!(message_limit > 0, "`message_limit` is `0`");
+		MessageQueue{ payloads: SliceQueue::with_limit(limit), lengths: VecDeque::new(), message_limit }
+	}
+	
+	
+	/// Sets a new element limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that can be stored by `self`
+	pub fn set_limit(&mut self, limit: usize) {
+		self.payloads.set_limit(limit)
+	}
+	/// The current element limit
+	///
+	/// Returns __the maximum amount of elements that can be stored by `self`__
+	pub fn limit(&self) -> usize {
+		self.payloads.limit()
+	}
+	
+	/// Sets a new message limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `message_limit` is `0`__
+	///
+	/// Parameters:
+	///  - `message_limit`: The maximum amount of messages that can be stored by `self`
+	pub fn set_message_limit(&mut self, message_limit: usize) {
+		assert!(message_limit > 0, "`message_limit` is `0`");
+		self.message_limit = message_limit
+	}
+	/// The current message limit
+	///
+	/// Returns __the maximum amount of messages that can be stored by `self`__
+	pub fn message_limit(&self) -> usize {
+		self.message_limit
+	}
+	
+	
+	/// The amount of elements stored in all messages
+	///
+	/// Returns __the amount of elements stored in `self`__
+	pub fn len(&self) -> usize {
+		self.payloads.len()
+	}
+	/// Checks if there are __no__ messages stored
+	///
+	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
+	pub fn is_empty(&self) -> bool {
+		self.lengths.is_empty()
+	}
+	/// The amount of messages stored
+	///
+	/// Returns __the amount of messages stored in `self`__
+	pub fn message_count(&self) -> usize {
+		self.lengths.len()
+	}
+	
+	/// The amount of elements that can be pushed until the element limit is reached
+	///
+	/// Returns __the amount of elements remaining until the element limit is reached__
+	pub fn remaining(&self) -> usize {
+		self.payloads.remaining()
+	}
+	/// The amount of messages that can be pushed until the message limit is reached
+	///
+	/// Returns __the amount of messages remaining until the message limit is reached__
+	pub fn remaining_messages(&self) -> usize {
+		self.message_limit.saturating_sub(self.lengths.len())
+	}
+	
+	
+	/// Appends `message` as new message at the end
+	///
+	/// Parameters:
+	///  - `message`: The n elements that form the message
+	///
+	/// Returns either __`Ok(())`__ if the message was appended or __`Err(message)`__ if it was not
+	/// appended because a limit would have been exceeded
+	pub fn push_message_n(&mut self, message: Vec<T>) -> Result<(), Vec<T>> {
+		if self.remaining_messages() < 1 || self.remaining() < message.len() { return Err(message) }
+		
+		self.lengths.push_back(message.len());
+		self.payloads.push_n(message).map_err(|_| unreachable!("The message fits into the payload queue"))
+	}
+	/// Clones and appends the elements in `message` as new message at the end
+	///
+	/// Parameters:
+	///  - `message`: A slice containing the elements that form the message
+	///
+	/// Returns either __`Ok(())`__ if the message was appended or __`Err(message)`__ if it was not
+	/// appended because a limit would have been exceeded
+	pub fn push_message<'a>(&mut self, message: &'a [T]) -> Result<(), &'a [T]> where T: Clone {
+		if self.remaining_messages() < 1 || self.remaining() < message.len() { return Err(message) }
+		
+		self.lengths.push_back(message.len());
+		self.payloads.push_from(message).map_err(|_| unreachable!("The message fits into the payload queue"))
+	}
+	
+	
+	/// Take a look at the first message __without__ consuming it
+	///
+	/// Returns either _`Some(message)`_ if we have a first message or _`None`_ otherwise
+	pub fn peek_message(&self) -> Option<&[T]> {
+		self.lengths.front().map(|len| &self.payloads[..*len])
+	}
+	/// The length of the first message
+	///
+	/// Returns either _`Some(message_len)`_ if we have a first message or _`None`_ otherwise
+	pub fn peek_message_len(&self) -> Option<usize> {
+		self.lengths.front().cloned()
+	}
+	
+	/// Consumes the first message and returns it
+	///
+	/// Returns either __`Some(message)`__ if there was a message to consume or __`None`__ otherwise
+	pub fn pop_message(&mut self) -> Option<Vec<T>> {
+		let len = self.lengths.pop_front()?;
+		Some(self.payloads.pop_n(len).unwrap_or_else(|_| unreachable!("The message is buffered completely")))
+	}
+	/// Consumes the first message and moves it into the beginning of `dst`
+	///
+	/// _Info: If `dst` is too small, the message remains in `self`_
+	///
+	/// Parameters:
+	///  - `dst`: The target to move the message into
+	///
+	/// Returns either __`Ok(message_len)`__ if the message was moved into `dst` or
+	/// __`Err(message_len)`__ if `dst` is too small (`Err(0)` indicates that there is no message;
+	/// an empty message always fits)
+	pub fn pop_message_into(&mut self, dst: &mut[T]) -> Result<usize, usize> {
+		match self.lengths.front().cloned() {
+			Some(len) if len <= dst.len() => {
+				self.lengths.pop_front();
+				self.payloads.pop_into(&mut dst[..len]).expect("Failed to pop buffered message");
+				Ok(len)
+			},
+			Some(len) => Err(len),
+			None => Err(0)
+		}
+	}
+	/// Discards the first `n` messages
+	///
+	/// Parameters:
+	///  - `n`: The amount of messages to discard
+	///
+	/// Returns either __`Ok(())`__ if `n` messages were discarded or __`Err(message_count)`__ if
+	/// only `message_count` messages were discarded
+	pub fn drop_messages(&mut self, n: usize) -> Result<(), usize> {
+		let to_drop = min(n, self.lengths.len());
+		let len = self.lengths.drain(..to_drop).sum();
+		self.payloads.drop_n(len).expect("Failed to discard buffered messages");
+		
+		if to_drop == n { Ok(()) }
+			else { Err(to_drop) }
+	}
+	
+	
+	/// An iterator over all buffered messages (from the first to the last message)
+	///
+	/// Returns __an iterator over the messages__
+	pub fn messages(&self) -> impl Iterator<Item = &[T]> {
+		let mut offset = 0;
+		self.lengths.iter().map(move |len| {
+			offset += len;
+			&self.payloads[offset - len .. offset]
+		})
+	}
+}
+impl<T> Default for MessageQueue<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
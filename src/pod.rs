@@ -0,0 +1,143 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue, Incomplete, mem::{ reinterpret_vec, copy_vec, wipe_spare } };
+use std::{ mem, ptr, slice, convert::TryFrom, error::Error, fmt::{ Display, Formatter, Result as FmtResult } };
+
+
+/// A marker trait for plain-old-data types that can be reinterpreted from and to raw bytes
+///
+/// # Safety
+/// The implementing type must not contain any padding bytes and every bit pattern must be a valid
+/// value of the type (this is for example __not__ the case for `bool`, `char`, references or most
+/// enums)
+pub unsafe trait Pod: Copy + 'static {}
+macro_rules! impl_pod {
+	($($ty:ty),*) => ($( unsafe impl Pod for $ty {} )*)
+}
+impl_pod!(u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize, f32, f64);
+/// _Info: Arrays of length `0` are zero-sized; `SliceQueue::cast` rejects zero-sized types._
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
+
+/// An error that occurred while accessing a record in place
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PodError {
+	/// Not enough bytes are available (yet)
+	Incomplete(Incomplete),
+	/// The record is not aligned to the required alignment (use `pop_record` to copy it instead)
+	Misaligned{ align: usize }
+}
+impl Display for PodError {
+	fn fmt(&self, f: &mut Formatter) -> FmtResult {
+		match self {
+			PodError::Incomplete(incomplete) => write!(f, "{}", incomplete),
+			PodError::Misaligned{ align } => write!(f, "The record is not aligned to {} bytes", align)
+		}
+	}
+}
+impl Error for PodError {}
+impl From<Incomplete> for PodError {
+	fn from(incomplete: Incomplete) -> Self {
+		PodError::Incomplete(incomplete)
+	}
+}
+
+
+impl SliceQueue<u8> {
+	/// Appends the raw bytes of `record` at the end
+	///
+	/// Parameters:
+	///  - `record`: The record to append
+	///
+	/// Returns either __`Ok(())`__ if the record was appended or __`Err(record)`__ if `record` was
+	/// not appended because the limit would have been exceeded
+	pub fn push_record<R: Pod>(&mut self, record: &R) -> Result<(), R> {
+		let bytes = unsafe{ slice::from_raw_parts(record as *const R as *const u8, mem::size_of::<R>()) };
+		if self.remaining() < bytes.len() { return Err(*record) }
+		self.push_from(bytes).map_err(|_| unreachable!("The record fits into the queue"))
+	}
+	
+	/// Take a look at the first record __without__ consuming or copying it
+	///
+	/// _Info: This requires the first byte to be aligned to `R`'s alignment; use `pop_record` to
+	/// read unaligned records._
+	///
+	/// Returns either __`Ok(&record)`__ or __`Err(PodError)`__ if there are not enough bytes or the
+	/// record is misaligned
+	pub fn peek_record<R: Pod>(&self) -> Result<&R, PodError> {
+		let bytes = self.peek_n(mem::size_of::<R>()).map_err(|available| {
+			Incomplete{ needed: mem::size_of::<R>(), available: available.len() }
+		})?;
+		
+		match bytes.as_ptr() as usize % mem::align_of::<R>() {
+			0 => Ok(unsafe{ &*(bytes.as_ptr() as *const R) }),
+			_ => Err(PodError::Misaligned{ align: mem::align_of::<R>() })
+		}
+	}
+	/// Consumes the first `mem::size_of::<R>()` bytes and copies them into a new record
+	///
+	/// _Info: The record does not need to be aligned._
+	///
+	/// Returns either __`Ok(record)`__ or __`Err(Incomplete)`__ if there are not enough bytes
+	pub fn pop_record<R: Pod>(&mut self) -> Result<R, Incomplete> {
+		let record = {
+			let bytes = self.peek_n(mem::size_of::<R>()).map_err(|available| {
+				Incomplete{ needed: mem::size_of::<R>(), available: available.len() }
+			})?;
+			unsafe{ ptr::read_unaligned(bytes.as_ptr() as *const R) }
+		};
+		self.drop_n(mem::size_of::<R>()).unwrap_or_else(|_| unreachable!("The record is buffered completely"));
+		Ok(record)
+	}
+}
+
+
+impl<T: Pod> SliceQueue<T> {
+	/// Converts `self` into a `SliceQueue<U>` by reinterpreting the buffered elements
+	///
+	/// _Info: If `T` and `U` have the same alignment and the allocated capacity is a multiple of
+	/// `U`'s size, the backing is reinterpreted in place; otherwise the elements are copied into a
+	/// new backing. The limit and the history limit are converted to the amount of `U`s that fit
+	/// into the old limits; the auto-shrink mode and the zeroize mode are preserved._
+	///
+	/// _Info: `consumed_total` is converted to `U`s if the consumed bytes are a multiple of `U`'s
+	/// size; otherwise the absolute offsets restart at `0`. The recorded history is discarded._
+	///
+	/// Returns either __`Ok(converted)`__ or __`Err(self)`__ if `T` or `U` is zero-sized or the
+	/// length of `self` in bytes is not a multiple of `U`'s size
+	pub fn cast<U: Pod>(mut self) -> Result<SliceQueue<U>, Self> {
+		let (size_t, size_u) = (mem::size_of::<T>(), mem::size_of::<U>());
+		if size_t == 0 || size_u == 0 || (self.len() * size_t) % size_u != 0 { return Err(self) }
+		
+		// Compute the new limits and the new absolute offset and convert the backing
+		let (limit, auto_shrink_mode) = (self.limit(), self.auto_shrink_mode());
+		let limit = match limit {
+			usize::MAX => usize::MAX,
+			limit => limit.checked_mul(size_t).map_or(usize::MAX, |bytes| (bytes / size_u).max(1))
+		};
+		let history_limit = self.history_limit().checked_mul(size_t).map_or(usize::MAX, |bytes| bytes / size_u);
+		let consumed = match self.consumed_total() as u128 * size_t as u128 {
+			bytes if bytes % size_u as u128 == 0 => u64::try_from(bytes / size_u as u128).unwrap_or(0),
+			_ => 0
+		};
+		let zeroize = self.zeroize();
+		if zeroize { self.wipe_history() }
+		let backing: Vec<T> = self.into();
+		let backing = unsafe{ reinterpret_vec(backing) }.unwrap_or_else(|mut backing| {
+			let copied = unsafe{ copy_vec(&backing) };
+			if zeroize {
+				let capacity = backing.capacity();
+				backing.clear();
+				wipe_spare(&mut backing, capacity)
+			}
+			copied
+		});
+		
+		// Create the new queue
+		let mut converted = SliceQueue::from(backing);
+		converted.set_limit(limit);
+		converted.set_auto_shrink_mode(auto_shrink_mode);
+		converted.set_zeroize(zeroize);
+		converted.set_history_limit(history_limit);
+		converted.set_consumed_total(consumed);
+		Ok(converted)
+	}
+}
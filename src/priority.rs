@@ -0,0 +1,356 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::cmp::min;
+
+
+/// The order in which the lanes are drained
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Scheduling {
+	/// Drains the lanes strictly by priority (a lower lane is only drained if all higher lanes are
+	/// empty)
+	///
+	/// __This mode is the default value__
+	Strict,
+	/// Drains the lanes by priority, but every non-empty lane gets `weight` elements per round, so
+	/// that lower lanes do not starve
+	Weighted
+}
+
+
+/// A single priority lane
+#[derive(Clone, Debug)]
+struct Lane<T> {
+	queue: SliceQueue<T>,
+	reservation: usize,
+	weight: usize,
+	credit: usize
+}
+
+
+/// A queue with multiple priority lanes that share one limit
+///
+/// Lane `0` has the highest priority. Every lane can have a reservation (i.e. an amount of space
+/// that can only be used by this lane); the space that is not reserved is shared between all lanes.
+///
+/// Example:
+/// ```
+/// # extern crate slice_queue;
+/// # use slice_queue::*;
+/// let mut queue = PrioritySliceQueue::new(2);
+/// queue.push_from(1, b"bulk").unwrap();
+/// queue.push_from(0, b"ctl").unwrap();
+/// assert_eq!(queue.pop_n(5).unwrap(), b"ctlbu");
+/// ```
+#[derive(Clone, Debug)]
+pub struct PrioritySliceQueue<T> {
+	lanes: Vec<Lane<T>>,
+	scheduling: Scheduling,
+	limit: usize
+}
+impl<T> PrioritySliceQueue<T> {
+	/// Creates a new `PrioritySliceQueue`
+	///
+	/// __Warning: Panics if `lanes` is `0`__
+	///
+	/// Parameters:
+	///  - `lanes`: The amount of priority lanes
+	///
+	/// Returns __the new `PrioritySliceQueue`__
+	pub fn new(lanes: usize) -> Self {
+		Self::with_limit(lanes, usize::MAX)
+	}
+	/// Creates a new `PrioritySliceQueue` with a predefined limit (the default limit is `usize::MAX`)
+	///
+	/// __Warning: Panics if `lanes` or `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `lanes`: The amount of priority lanes
+	///  - `limit`: The maximum amount of elements that can be stored in all lanes
+	///
+	/// Returns __the new `PrioritySliceQueue`__
+	pub fn with_limit(lanes: usize, limit: usize) -> Self {
+		assert!(lanes > 0, "`lanes` is `0`");
+		assert!(limit > 0, "`limit` is `0`");
+		
+		let lanes = (0..lanes).map(|_| Lane{ queue: SliceQueue::new(), reservation: 0, weight: 1, credit: 0 }).collect();
+		PrioritySliceQueue{ lanes, scheduling: Scheduling::Strict, limit }
+	}
+	
+	
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0` or smaller than the sum of all reservations__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that can be stored in all lanes
+	pub fn set_limit(&mut self, limit: usize) {
+		assert!(limit > 0, "`limit` is `0`");
+		assert!(limit >= self.reserved_total(), "`limit` is smaller than the reservations");
+		self.limit = limit
+	}
+	/// The current limit
+	///
+	/// Returns __the maximum amount of elements that can be stored in all lanes__
+	pub fn limit(&self) -> usize {
+		self.limit
+	}
+	/// Sets the reservation of a lane (the default reservation is `0`)
+	///
+	/// __Warning: Panics if `lane` is out of range or if the sum of all reservations would exceed
+	/// the limit__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///  - `reservation`: The amount of space that can only be used by `lane`
+	pub fn set_reservation(&mut self, lane: usize, reservation: usize) {
+		let others = self.reserved_total() - self.lane(lane).reservation;
+		assert!(others.checked_add(reservation).is_some_and(|total| total <= self.limit), "The reservations exceed the limit");
+		self.lane_mut(lane).reservation = reservation
+	}
+	/// The reservation of a lane
+	///
+	/// __Warning: Panics if `lane` is out of range__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///
+	/// Returns __the amount of space that can only be used by `lane`__
+	pub fn reservation(&self, lane: usize) -> usize {
+		self.lane(lane).reservation
+	}
+	/// Sets the scheduling mode (the default mode is `Scheduling::Strict`)
+	///
+	/// Parameters:
+	///  - `scheduling`: The order in which the lanes are drained
+	pub fn set_scheduling(&mut self, scheduling: Scheduling) {
+		self.scheduling = scheduling
+	}
+	/// The current scheduling mode
+	///
+	/// Returns __the order in which the lanes are drained__
+	pub fn scheduling(&self) -> Scheduling {
+		self.scheduling
+	}
+	/// Sets the weight of a lane (the default weight is `1`)
+	///
+	/// _Info: The weight is only used with `Scheduling::Weighted`; it takes effect in the next
+	/// round._
+	///
+	/// __Warning: Panics if `lane` is out of range or `weight` is `0`__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///  - `weight`: The amount of elements `lane` gets per round
+	pub fn set_weight(&mut self, lane: usize, weight: usize) {
+		assert!(weight > 0, "`weight` is `0`");
+		self.lane_mut(lane).weight = weight
+	}
+	/// The weight of a lane
+	///
+	/// __Warning: Panics if `lane` is out of range__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///
+	/// Returns __the amount of elements `lane` gets per round__
+	pub fn weight(&self, lane: usize) -> usize {
+		self.lane(lane).weight
+	}
+	
+	
+	/// The amount of lanes
+	///
+	/// Returns __the amount of lanes__
+	pub fn lanes(&self) -> usize {
+		self.lanes.len()
+	}
+	/// The amount of elements stored in all lanes
+	///
+	/// Returns __the amount of elements stored in `self`__
+	pub fn len(&self) -> usize {
+		self.lanes.iter().map(|lane| lane.queue.len()).sum()
+	}
+	/// Checks if there are __no__ elements stored in any lane
+	///
+	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
+	pub fn is_empty(&self) -> bool {
+		self.lanes.iter().all(|lane| lane.queue.is_empty())
+	}
+	/// The amount of elements stored in a lane
+	///
+	/// __Warning: Panics if `lane` is out of range__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///
+	/// Returns __the amount of elements stored in `lane`__
+	pub fn lane_len(&self, lane: usize) -> usize {
+		self.lane(lane).queue.len()
+	}
+	/// The amount of elements that can be pushed into a lane until the limit is reached
+	///
+	/// __Warning: Panics if `lane` is out of range__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///
+	/// Returns __the unused reservation of `lane` plus the unused shared space__
+	pub fn remaining(&self, lane: usize) -> usize {
+		let lane = self.lane(lane);
+		let used_shared: usize = self.lanes.iter().map(|lane| lane.queue.len().saturating_sub(lane.reservation)).sum();
+		let shared_free = (self.limit - self.reserved_total()).saturating_sub(used_shared);
+		lane.reservation.saturating_sub(lane.queue.len()).saturating_add(shared_free)
+	}
+	
+	
+	/// Appends `element` at the end of a lane
+	///
+	/// __Warning: Panics if `lane` is out of range__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///  - `element`: The element to append
+	///
+	/// Returns either __`Ok(())`__ if the element was pushed successfully or __`Err(element)`__ if
+	/// `element` was not appended because the limit would have been exceeded
+	pub fn push(&mut self, lane: usize, element: T) -> Result<(), T> {
+		if self.remaining(lane) == 0 { return Err(element) }
+		self.lane_mut(lane).queue.push(element).unwrap_or_else(|_| unreachable!("The lanes are unlimited"));
+		Ok(())
+	}
+	/// Appends `n` at the end of a lane
+	///
+	/// __Warning: Panics if `lane` is out of range__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///  - `n`: The n elements to append
+	///
+	/// Returns either __`Ok(())`__ if `n` was appended completely or __`Err(remaining_elements)`__
+	/// if `n` was only appended partially because the limit would have been exceeded
+	pub fn push_n(&mut self, lane: usize, mut n: Vec<T>) -> Result<(), Vec<T>> {
+		let remaining = n.split_off(min(n.len(), self.remaining(lane)));
+		self.lane_mut(lane).queue.push_n(n).unwrap_or_else(|_| unreachable!("The lanes are unlimited"));
+		
+		if remaining.is_empty() { Ok(()) }
+			else { Err(remaining) }
+	}
+	
+	/// Consumes the first element of the highest-priority lane (according to the scheduling mode)
+	///
+	/// Returns either __`Some(element)`__ or __`None`__ if `self` is empty
+	pub fn pop(&mut self) -> Option<T> {
+		let (lane, _) = self.next_run(1)?;
+		self.lanes[lane].queue.pop().ok()
+	}
+	/// Consumes the first `n` elements across the lanes (according to the scheduling mode)
+	///
+	/// Parameters:
+	///  - `n`: The amount of elements to consume
+	///
+	/// Returns either __`Ok(elements)`__ if there were `n` elements available to consume or
+	/// __`Err(elements)`__ if less elements were available
+	pub fn pop_n(&mut self, n: usize) -> Result<Vec<T>, Vec<T>> {
+		let mut elements = Vec::with_capacity(min(n, self.len()));
+		while let Some((lane, count)) = self.next_run(n - elements.len()) {
+			elements.append(&mut self.lanes[lane].queue.pop_n(count).unwrap_or_else(|_| unreachable!("The elements are available")));
+		}
+		
+		if elements.len() == n { Ok(elements) }
+			else { Err(elements) }
+	}
+	/// Consumes the first `dst.len()` elements across the lanes (according to the scheduling mode)
+	/// and moves them into `dst`
+	///
+	/// Parameters:
+	///  - `dst`: The target to move the elements into
+	///
+	/// Returns either __`Ok(())`__ if `dst` was filled completely or __`Err(element_count)`__ if
+	/// only `element_count` elements were moved
+	pub fn pop_into(&mut self, dst: &mut[T]) -> Result<(), usize> {
+		let mut moved = 0;
+		while let Some((lane, count)) = self.next_run(dst.len() - moved) {
+			self.lanes[lane].queue.pop_into(&mut dst[moved .. moved + count]).unwrap_or_else(|_| unreachable!("The elements are available"));
+			moved += count;
+		}
+		
+		if moved == dst.len() { Ok(()) }
+			else { Err(moved) }
+	}
+	/// Discards the first `n` elements across the lanes (according to the scheduling mode)
+	///
+	/// Parameters:
+	///  - `n`: The amount of elements to discard
+	///
+	/// Returns either __`Ok(())`__ if `n` elements were discarded or __`Err(element_count)`__ if
+	/// only `element_count` elements were discarded
+	pub fn drop_n(&mut self, n: usize) -> Result<(), usize> {
+		let mut dropped = 0;
+		while let Some((lane, count)) = self.next_run(n - dropped) {
+			self.lanes[lane].queue.drop_n(count).unwrap_or_else(|_| unreachable!("The elements are available"));
+			dropped += count;
+		}
+		
+		if dropped == n { Ok(()) }
+			else { Err(dropped) }
+	}
+	
+	
+	/// The lane at index `lane`
+	fn lane(&self, lane: usize) -> &Lane<T> {
+		self.lanes.get(lane).unwrap_or_else(|| panic!("`lane` is out of range"))
+	}
+	/// The mutable lane at index `lane`
+	fn lane_mut(&mut self, lane: usize) -> &mut Lane<T> {
+		self.lanes.get_mut(lane).unwrap_or_else(|| panic!("`lane` is out of range"))
+	}
+	/// The sum of all reservations
+	fn reserved_total(&self) -> usize {
+		self.lanes.iter().map(|lane| lane.reservation).sum()
+	}
+	/// Selects the lane to consume the next (up to `max`) elements from and charges the consumed
+	/// elements against the lane's credit
+	///
+	/// Returns either __`Some((lane, count))`__ or __`None`__ if `max` is `0` or `self` is empty
+	fn next_run(&mut self, max: usize) -> Option<(usize, usize)> {
+		if max == 0 || self.is_empty() { return None }
+		match self.scheduling {
+			Scheduling::Strict => {
+				let lane = self.lanes.iter().position(|lane| !lane.queue.is_empty())?;
+				Some((lane, min(max, self.lanes[lane].queue.len())))
+			},
+			Scheduling::Weighted => {
+				// Start a new round if no non-empty lane has credit left
+				if !self.lanes.iter().any(|lane| !lane.queue.is_empty() && lane.credit > 0) {
+					self.lanes.iter_mut().for_each(|lane| lane.credit = lane.weight);
+				}
+				
+				let index = self.lanes.iter().position(|lane| !lane.queue.is_empty() && lane.credit > 0)?;
+				let lane = &mut self.lanes[index];
+				let count = min(max, min(lane.queue.len(), lane.credit));
+				lane.credit -= count;
+				Some((index, count))
+			}
+		}
+	}
+}
+impl<T: Clone> PrioritySliceQueue<T> {
+	/// Clones and appends the elements in `src` at the end of a lane
+	///
+	/// __Warning: Panics if `lane` is out of range__
+	///
+	/// Parameters:
+	///  - `lane`: The lane
+	///  - `src`: A slice containing the elements to clone and append
+	///
+	/// Returns either __`Ok(())`__ if `src` was appended completely or __`Err(appended_count)`__ if
+	/// `src` was only appended partially because the limit would have been exceeded
+	pub fn push_from(&mut self, lane: usize, src: &[T]) -> Result<(), usize> {
+		let to_push = min(src.len(), self.remaining(lane));
+		self.lane_mut(lane).queue.push_from(&src[..to_push]).unwrap_or_else(|_| unreachable!("The lanes are unlimited"));
+		
+		if to_push == src.len() { Ok(()) }
+			else { Err(to_push) }
+	}
+}
@@ -29,18 +29,27 @@ impl Default for AutoShrinkMode {
 }
 
 
+/// A function that records consumed elements in the history
+type HistoryRecordFn<T> = fn(&mut Vec<T>, &[T], usize);
+
+
 #[derive(Default)]
 pub struct SliceQueue<T> {
 	backing: Vec<T>,
 	limit: usize,
-	auto_shrink_mode: AutoShrinkMode
+	auto_shrink_mode: AutoShrinkMode,
+	consumed: u64,
+	history: Vec<T>,
+	history_limit: usize,
+	history_record: Option<HistoryRecordFn<T>>,
+	zeroize: bool
 }
 impl<T> SliceQueue<T> {
 	/// Creates a new `SliceQueue`
 	///
 	/// Returns __the new `SliceQueue`__
 	pub fn new() -> Self {
-		SliceQueue{ backing: Vec::new(), limit: usize::MAX, auto_shrink_mode: Default::default() }
+		Self::with_backing(Vec::new(), usize::MAX)
 	}
 	/// Creates a new `SliceQueue` with a preallocated capacity `n`
 	///
@@ -49,7 +58,7 @@ impl<T> SliceQueue<T> {
 	///
 	/// Returns __the new `SliceQueue`__
 	pub fn with_capacity(n: usize) -> Self {
-		SliceQueue{ backing: Vec::with_capacity(n), limit: usize::MAX, auto_shrink_mode: Default::default() }
+		Self::with_backing(Vec::with_capacity(n), usize::MAX)
 	}
 	/// Creates a new `SliceQueue` with a predefined `limit` (the default limit is `usize::MAX`)
 	///
@@ -62,7 +71,14 @@ impl<T> SliceQueue<T> {
 	/// Returns __the new `SliceQueue`__
 	pub fn with_limit(limit: usize) -> Self {
 		assert!(limit > 0, "`limit` is `0`");
-		SliceQueue{ backing: Vec::new(), limit, auto_shrink_mode: Default::default() }
+		Self::with_backing(Vec::new(), limit)
+	}
+	/// Creates a new `SliceQueue` with `backing` and `limit`
+	fn with_backing(backing: Vec<T>, limit: usize) -> Self {
+		SliceQueue {
+			backing, limit, auto_shrink_mode: Default::default(), consumed: 0,
+			history: Vec::new(), history_limit: 0, history_record: None, zeroize: false
+		}
 	}
 	
 	
@@ -83,6 +99,40 @@ impl<T> SliceQueue<T> {
 		self.auto_shrink_mode
 	}
 	
+	/// Enables or disables the zeroize mode (which is disabled by default)
+	///
+	/// If the zeroize mode is enabled, the memory vacated by consuming elements and every old
+	/// allocation on reallocation are overwritten with zeroes using volatile writes. This includes
+	/// the history.
+	///
+	/// _Info: Only the memory of the elements themselves is wiped; memory that is owned by the
+	/// elements (e.g. the buffer of a `Vec<u8>`) is not wiped. Memory that was freed before the
+	/// zeroize mode was enabled is not wiped either._
+	///
+	/// _Info: The auto-shrink mode still applies, so consuming elements may move the remaining
+	/// elements into a new allocation; the old allocation is wiped before it is freed. Disable
+	/// auto-shrinking (`AutoShrinkMode::Disabled`) to keep the elements in a single allocation._
+	///
+	/// _Info: The zeroize mode requires the `unsafe_fast_code`-feature; without it this method does
+	/// not exist and memory is never wiped._
+	///
+	/// _Info: The allocations are __not__ wiped when the `SliceQueue` is dropped; use
+	/// `ZeroizingSliceQueue` to also wipe them on drop._
+	///
+	/// Parameters:
+	///  - `zeroize`: Whether the zeroize mode should be enabled
+	#[cfg(feature = "unsafe_fast_code")]
+	pub fn set_zeroize(&mut self, zeroize: bool) {
+		self.zeroize = zeroize
+	}
+	/// Whether the zeroize mode is enabled
+	///
+	/// Returns either __`true`__ if the zeroize mode is enabled or __`false`__ otherwise
+	#[cfg(feature = "unsafe_fast_code")]
+	pub fn zeroize(&self) -> bool {
+		self.zeroize
+	}
+	
 	
 	/// Sets a new limit (the default limit is `usize::MAX`)
 	///
@@ -114,11 +164,17 @@ impl<T> SliceQueue<T> {
 			else { self.backing.capacity() / 2 };
 		
 		// Resize the backing if the used space is smaller than the half capacity
-		if self.len() > 4 && (self.len() <= half_capacity || self.backing.capacity() > self.limit) { self.backing.shrink_to_fit() }
+		if self.len() > 4 && (self.len() <= half_capacity || self.backing.capacity() > self.limit) {
+			mem::shrink_to_fit(&mut self.backing, self.zeroize)
+		}
+		
+		// Release the history's memory if it is much larger than the history limit
+		if self.history.capacity() / 4 > self.history_limit { self.shrink_history() }
 	}
 	/// Shrinks the allocated capacity as much as possible
 	pub fn shrink_to_fit(&mut self) {
-		self.backing.shrink_to_fit()
+		mem::shrink_to_fit(&mut self.backing, self.zeroize);
+		self.shrink_history()
 	}
 	/// Performs the auto-shrink action specified by `self.auto_shrink_mode`
 	pub fn auto_shrink(&mut self) {
@@ -128,6 +184,211 @@ impl<T> SliceQueue<T> {
 			AutoShrinkMode::Disabled => ()
 		}
 	}
+	
+	
+	/// The total amount of elements consumed since `self` was created (this is also the absolute
+	/// offset of the first element)
+	///
+	/// _Info: Elements that are removed by directly modifying the underlying `Vec<T>` (e.g. using
+	/// `deref_mut`) are not tracked._
+	///
+	/// Returns __the total amount of elements consumed__
+	pub fn consumed_total(&self) -> u64 {
+		self.consumed
+	}
+	/// The total amount of elements pushed since `self` was created (this is also the absolute
+	/// offset of the next element to push)
+	///
+	/// Returns __the total amount of elements pushed__
+	pub fn pushed_total(&self) -> u64 {
+		self.consumed + self.len() as u64
+	}
+	/// Sets the total amount of elements consumed (e.g. to carry the absolute offsets over to a
+	/// converted queue)
+	#[cfg(feature = "unsafe_fast_code")]
+	pub(crate) fn set_consumed_total(&mut self, consumed: u64) {
+		self.consumed = consumed
+	}
+	/// Gets the element at the absolute `offset`
+	///
+	/// Parameters:
+	///  - `offset`: The absolute offset of the element within the stream
+	///
+	/// Returns either __`Some(element_ref)`__ or __`None`__ if the element has already been
+	/// consumed or has not been pushed yet
+	pub fn get_abs(&self, offset: u64) -> Option<&T> {
+		match offset.checked_sub(self.consumed) {
+			Some(index) if index < self.len() as u64 => Some(&self.backing[index as usize]),
+			_ => None
+		}
+	}
+	/// Take a look at the elements within the absolute `range` __without__ consuming them
+	///
+	/// Parameters:
+	///  - `range`: The absolute range of the elements within the stream
+	///
+	/// Returns either __`Some(element_refs)`__ or __`None`__ if the range is invalid or some of
+	/// the elements have already been consumed or have not been pushed yet
+	pub fn peek_abs(&self, range: Range<u64>) -> Option<&[T]> {
+		match (range.start.checked_sub(self.consumed), range.end.checked_sub(self.consumed)) {
+			(Some(start), Some(end)) if start <= end && end <= self.len() as u64 => {
+				Some(&self.backing[start as usize .. end as usize])
+			},
+			_ => None
+		}
+	}
+	/// Discards all elements before the absolute `offset`
+	///
+	/// Parameters:
+	///  - `offset`: The absolute offset of the first element to keep
+	///
+	/// Returns either __`Ok(())`__ if all elements before `offset` are discarded (or have already
+	/// been consumed) or __`Err(element_count)`__ if only `element_count` elements were discarded
+	/// because `offset` is beyond the last element
+	pub fn drop_until_abs(&mut self, offset: u64) -> Result<(), usize> {
+		let n = min(offset.saturating_sub(self.consumed), usize::MAX as u64);
+		self.drop_n(n as usize)
+	}
+	
+	
+	/// The amount of consumed elements available in the history
+	///
+	/// _Info: The history counts towards the memory used by `self` but not towards `self.len()`
+	/// or `self.limit`._
+	///
+	/// Returns __the amount of consumed elements that can be accessed using `self.history`__
+	pub fn history_len(&self) -> usize {
+		min(self.history.len(), self.history_limit)
+	}
+	/// The current history limit
+	///
+	/// Returns __the maximum amount of consumed elements that are kept__
+	pub fn history_limit(&self) -> usize {
+		self.history_limit
+	}
+	/// Take a look at the last `n` consumed elements
+	///
+	/// Parameters:
+	///  - `n`: The amount of consumed elements to look at
+	///
+	/// Returns either __`Ok(element_refs)`__ if there were `n` elements in the history or
+	/// __`Err(element_refs)`__ if less elements were available
+	pub fn history(&self, n: usize) -> Result<&[T], &[T]> {
+		let available = self.history_len();
+		match n <= available {
+			true => Ok(&self.history[self.history.len() - n..]),
+			false => Err(&self.history[self.history.len() - available..])
+		}
+	}
+	
+	/// Records the first `n` elements as history if the history is enabled
+	fn record_history(&mut self, n: usize) {
+		if let Some(record) = self.history_record {
+			// Reserve the memory first so that a reallocation can be wiped
+			let recorded = min(n, self.history_limit);
+			mem::reserve(&mut self.history, recorded, self.zeroize);
+			let peak = self.history.len() + recorded;
+			
+			record(&mut self.history, &self.backing[..n], self.history_limit);
+			self.wipe_vacated_history(peak)
+		}
+	}
+	/// Removes the elements that exceed the history limit and releases the unused memory
+	fn shrink_history(&mut self) {
+		let (excess, old_len) = (self.history.len().saturating_sub(self.history_limit), self.history.len());
+		mem::drop_n(&mut self.history, excess);
+		self.wipe_vacated_history(old_len);
+		mem::shrink_to_fit(&mut self.history, self.zeroize)
+	}
+	
+	/// Wipes the slots between `self.len()` and `old_len` if the zeroize mode is enabled
+	fn wipe_vacated(&mut self, old_len: usize) {
+		if self.zeroize { mem::wipe_spare(&mut self.backing, old_len) }
+	}
+	/// Wipes the slots between `self.history.len()` and `old_len` if the zeroize mode is enabled
+	fn wipe_vacated_history(&mut self, old_len: usize) {
+		if self.zeroize { mem::wipe_spare(&mut self.history, old_len) }
+	}
+	
+	/// Drops all elements and the history and wipes the whole allocations
+	#[cfg(feature = "unsafe_fast_code")]
+	pub(crate) fn wipe(&mut self) {
+		self.backing.clear();
+		let capacity = self.backing.capacity();
+		mem::wipe_spare(&mut self.backing, capacity);
+		self.wipe_history()
+	}
+	/// Drops the history and wipes its whole allocation
+	#[cfg(feature = "unsafe_fast_code")]
+	pub(crate) fn wipe_history(&mut self) {
+		self.history.clear();
+		let capacity = self.history.capacity();
+		mem::wipe_spare(&mut self.history, capacity)
+	}
+}
+
+
+impl<T> SliceQueue<T> where T: Clone {
+	/// Sets the history limit (the default limit is `0`, which disables the history)
+	///
+	/// If the history is enabled, the last `history_limit` consumed elements are cloned and kept so
+	/// that they can be accessed using `self.history` or `self.copy_from_history`. The memory used
+	/// by the history is released according to the auto-shrink mode.
+	///
+	/// Parameters:
+	///  - `history_limit`: The maximum amount of consumed elements to keep
+	pub fn set_history_limit(&mut self, history_limit: usize) {
+		self.history_limit = history_limit;
+		self.history_record = match history_limit {
+			0 => None,
+			_ => Some(record_history::<T>)
+		};
+		self.auto_shrink()
+	}
+	
+	/// Clones `len` elements that start `distance` elements before the end and appends them at the
+	/// end (like a LZ77 back-reference)
+	///
+	/// _Info: The referenced elements may overlap with the appended elements (e.g. a `distance` of
+	/// `1` repeats the last element `len` times). Both the history and the elements stored in
+	/// `self` can be referenced._
+	///
+	/// __Warning: Panics if `distance` is `0` or greater than `self.history_len() + self.len()`__
+	///
+	/// Parameters:
+	///  - `distance`: The distance between the end and the first element to clone
+	///  - `len`: The amount of elements to append
+	///
+	/// Returns either __`Ok(())`__ if `len` elements were appended or __`Err(element_count)`__ if
+	/// only `element_count` elements were appended because `self.limit` would have been exceeded
+	pub fn copy_from_history(&mut self, distance: usize, len: usize) -> Result<(), usize> {
+		let (history_len, available) = (self.history_len(), self.history_len() + self.len());
+		assert!(distance > 0 && distance <= available, "`distance` is outside of the history");
+		
+		// Clone the elements one by one so that overlapping references repeat the pattern
+		let (start, history_offset) = (available - distance, self.history.len() - history_len);
+		let to_copy = min(len, self.remaining());
+		mem::reserve(&mut self.backing, to_copy, self.zeroize);
+		for position in start .. start + to_copy {
+			let element = match position < history_len {
+				true => self.history[history_offset + position].clone(),
+				false => self.backing[position - history_len].clone()
+			};
+			self.backing.push(element);
+		}
+		
+		if to_copy == len { Ok(()) }
+			else { Err(to_copy) }
+	}
+}
+/// Clones `consumed` into `history` and removes the excess elements if `history` has grown to twice
+/// the `limit`
+fn record_history<T: Clone>(history: &mut Vec<T>, consumed: &[T], limit: usize) {
+	history.extend_from_slice(&consumed[consumed.len().saturating_sub(limit)..]);
+	if history.len() >= limit.saturating_mul(2) {
+		let excess = history.len() - limit;
+		mem::drop_n(history, excess)
+	}
 }
 
 
@@ -144,6 +405,12 @@ impl<T> ReadableSliceQueue<T> for SliceQueue<T> {
 	fn is_empty(&self) -> bool {
 		self.backing.is_empty()
 	}
+	/// The maximum amount of elements that can be stored
+	///
+	/// Returns __the current size-limit of `self`__
+	fn limit(&self) -> usize {
+		self.limit
+	}
 	
 	/// Take a look at the first element __without__ consuming it
 	///
@@ -171,7 +438,10 @@ impl<T> ReadableSliceQueue<T> for SliceQueue<T> {
 		match self.is_empty() {
 			true => Err(()),
 			false => {
+				self.record_history(1);
 				let element = self.backing.remove(0);
+				self.wipe_vacated(self.backing.len() + 1);
+				self.consumed += 1;
 				self.auto_shrink();
 				Ok(element)
 			}
@@ -187,7 +457,10 @@ impl<T> ReadableSliceQueue<T> for SliceQueue<T> {
 	fn pop_n(&mut self, n: usize) -> Result<Vec<T>, Vec<T>> {
 		// Move elements into `elements`
 		let to_consume = min(self.len(), n);
+		self.record_history(to_consume);
 		let elements = mem::drain_n(&mut self.backing, to_consume);
+		self.wipe_vacated(self.backing.len() + to_consume);
+		self.consumed += to_consume as u64;
 		
 		// Shrink and return result
 		self.auto_shrink();
@@ -204,7 +477,10 @@ impl<T> ReadableSliceQueue<T> for SliceQueue<T> {
 	fn pop_into(&mut self, dst: &mut[T]) -> Result<(), usize> {
 		// Move elements
 		let to_move = min(self.len(), dst.len());
+		self.record_history(to_move);
 		mem::drain_into(&mut self.backing, &mut dst[..to_move]);
+		self.wipe_vacated(self.backing.len() + to_move);
+		self.consumed += to_move as u64;
 		
 		// Shrink and return result
 		self.auto_shrink();
@@ -222,7 +498,10 @@ impl<T> ReadableSliceQueue<T> for SliceQueue<T> {
 	fn drop_n(&mut self, n: usize) -> Result<(), usize> {
 		// Drop `n` elements and copy the remaining elements to the front
 		let to_drop = min(self.len(), n);
+		self.record_history(to_drop);
 		mem::drop_n(&mut self.backing, to_drop);
+		self.wipe_vacated(self.backing.len() + to_drop);
+		self.consumed += to_drop as u64;
 		
 		// Shrink and return result
 		self.auto_shrink();
@@ -273,7 +552,7 @@ impl<T> WriteableSliceQueue<T> for SliceQueue<T> {
 	fn reserve_n(&mut self, n: usize) -> Result<(), usize> {
 		// Reserve elements
 		let to_reserve = min(self.limit.checked_sub(self.backing.capacity()).unwrap_or_default(), n);
-		self.backing.reserve_exact(to_reserve);
+		mem::reserve_exact(&mut self.backing, to_reserve, self.zeroize);
 		
 		if to_reserve == n { Ok(()) }
 			else { Err(to_reserve) }
@@ -293,8 +572,11 @@ impl<T> WriteableSliceQueue<T> for SliceQueue<T> {
 	/// Returns either __`Ok(())`__ if the element was pushed successfully or __`Err(element)`__ if
 	/// `element` was not appended because `self.limit` would have been exceeded
 	fn push(&mut self, element: T) -> Result<(), T> {
-		if self.remaining() >= 1 { Ok(self.backing.push(element)) }
-			else { Err(element) }
+		if self.remaining() < 1 { return Err(element) }
+		
+		mem::reserve(&mut self.backing, 1, self.zeroize);
+		self.backing.push(element);
+		Ok(())
 	}
 	/// Appends `n` at the end
 	///
@@ -304,6 +586,9 @@ impl<T> WriteableSliceQueue<T> for SliceQueue<T> {
 	/// Returns either __`Ok(())`__ if `n` was appended completely or __`Err(remaining_elements)`__
 	/// if `n` was only appended partially because `self.limit` would have been exceeded
 	fn push_n(&mut self, mut n: Vec<T>) -> Result<(), Vec<T>> {
+		let to_append = min(self.remaining(), n.len());
+		mem::reserve(&mut self.backing, to_append, self.zeroize);
+		
 		if self.remaining() >= n.len() {
 			self.backing.append(&mut n);
 			Ok(())
@@ -323,6 +608,7 @@ impl<T> WriteableSliceQueue<T> for SliceQueue<T> {
 	/// would have been exceeded
 	fn push_from(&mut self, src: &[T]) -> Result<(), usize> where T: Clone {
 		let to_append = min(self.remaining(), src.len());
+		mem::reserve(&mut self.backing, to_append, self.zeroize);
 		self.backing.extend_from_slice(&src[..to_append]);
 		
 		if to_append == src.len() { Ok(()) }
@@ -372,7 +658,7 @@ impl<T> WriteableSliceQueue<T> for SliceQueue<T> {
 		let old_len = self.len();
 		
 		// Append `n` default elements
-		self.backing.reserve(n);
+		mem::reserve(&mut self.backing, n, self.zeroize);
 		(0..n).for_each(|_| self.backing.push(T::default()));
 		
 		// Call `push_fn` and truncate the length to the amount of elements pushed
@@ -382,6 +668,7 @@ impl<T> WriteableSliceQueue<T> for SliceQueue<T> {
 			Ok(pushed) => *pushed,
 			Err(_) => 0
 		});
+		self.wipe_vacated(old_len + n);
 		self.shrink_opportunistic();
 		
 		pushed
@@ -424,12 +711,12 @@ impl<T: Debug> Debug for SliceQueue<T> {
 
 impl<'a, T> From<&'a[T]> for SliceQueue<T> where T: Clone {
 	fn from(slice: &[T]) -> Self {
-		SliceQueue{ backing: slice.to_vec(), limit: usize::MAX, auto_shrink_mode: Default::default() }
+		Self::with_backing(slice.to_vec(), usize::MAX)
 	}
 }
 impl<T> From<Vec<T>> for SliceQueue<T> {
 	fn from(vec: Vec<T>) -> Self {
-		SliceQueue{ backing: vec, limit: usize::MAX, auto_shrink_mode: Default::default() }
+		Self::with_backing(vec, usize::MAX)
 	}
 }
 impl<T> Into<Vec<T>> for SliceQueue<T> {
@@ -441,7 +728,11 @@ impl<T> Into<Vec<T>> for SliceQueue<T> {
 
 impl<T> Clone for SliceQueue<T> where T: Clone {
 	fn clone(&self) -> Self {
-		SliceQueue{ backing: self.backing.clone(), limit: self.limit, auto_shrink_mode: Default::default() }
+		SliceQueue {
+			backing: self.backing.clone(), limit: self.limit, auto_shrink_mode: Default::default(),
+			consumed: self.consumed, history: self.history.clone(), history_limit: self.history_limit,
+			history_record: self.history_record, zeroize: self.zeroize
+		}
 	}
 }
 
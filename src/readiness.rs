@@ -0,0 +1,101 @@
+use std::{
+	fs::File, sync::{ Arc, Weak },
+	os::unix::io::{ AsRawFd, FromRawFd, RawFd },
+	io::{ self, Read, Write, Result as IoResult }
+};
+
+
+/// The raw Linux eventfd API
+mod sys {
+	use std::os::raw::{ c_int, c_uint };
+	
+	#[cfg(any(target_arch = "mips", target_arch = "mips64"))]
+	pub const EFD_NONBLOCK: c_int = 0x80;
+	#[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+	pub const EFD_NONBLOCK: c_int = 0x800;
+	pub const EFD_CLOEXEC: c_int = 0x80000;
+	
+	extern "C" {
+		pub fn eventfd(initval: c_uint, flags: c_int) -> c_int;
+	}
+}
+
+
+/// The eventfd counter value that makes the eventfd readable but not writeable
+const NOT_WRITEABLE: u64 = 0xffff_ffff_ffff_fffe;
+
+
+/// A pollable readiness handle for a `SharedSliceQueue`
+///
+/// The handle wraps an eventfd that is readable (`EPOLLIN`) if the queue is not empty and writeable
+/// (`EPOLLOUT`) if at least `threshold` elements can be pushed, so it can be registered with epoll,
+/// mio or any other poll loop. The state is updated by every call that changes the queue.
+///
+/// _Info: An eventfd is always readable or writeable, so the handle is also writeable if the queue
+/// is empty and less than `threshold` elements can be pushed._
+///
+/// __Warning: Do not read from or write to the file descriptor; this would corrupt the state__
+#[derive(Debug)]
+pub struct Readiness {
+	eventfd: Arc<File>,
+	threshold: usize
+}
+impl Readiness {
+	/// Creates a new readiness handle for a queue with `len` elements and `remaining` space
+	pub(crate) fn new(threshold: usize, len: usize, remaining: usize) -> IoResult<Self> {
+		let fd = unsafe{ sys::eventfd(0, sys::EFD_NONBLOCK | sys::EFD_CLOEXEC) };
+		if fd == -1 { return Err(io::Error::last_os_error()) }
+		
+		let readiness = Readiness{ eventfd: Arc::new(unsafe{ File::from_raw_fd(fd) }), threshold };
+		readiness.notifier().update(len, remaining);
+		Ok(readiness)
+	}
+	/// Creates a notifier that updates the state of `self` (as long as `self` is alive)
+	pub(crate) fn notifier(&self) -> Notifier {
+		Notifier{ eventfd: Arc::downgrade(&self.eventfd), threshold: self.threshold }
+	}
+	
+	/// The writeability threshold
+	///
+	/// Returns __the amount of elements that must fit into the queue for the handle to be
+	/// writeable__
+	pub fn threshold(&self) -> usize {
+		self.threshold
+	}
+}
+impl AsRawFd for Readiness {
+	fn as_raw_fd(&self) -> RawFd {
+		self.eventfd.as_raw_fd()
+	}
+}
+
+
+/// The queue's reference to a readiness handle
+#[derive(Debug)]
+pub struct Notifier {
+	eventfd: Weak<File>,
+	threshold: usize
+}
+impl Notifier {
+	/// Sets the eventfd counter according to `len` and `remaining`
+	///
+	/// Returns either __`true`__ if the handle is still alive or __`false`__ if it has been dropped
+	pub fn update(&self, len: usize, remaining: usize) -> bool {
+		let eventfd = match self.eventfd.upgrade() {
+			Some(eventfd) => eventfd,
+			None => return false
+		};
+		let counter = match (len > 0, remaining >= self.threshold) {
+			(false, _) => 0,
+			(true, true) => 1,
+			(true, false) => NOT_WRITEABLE
+		};
+		
+		// Reset the counter (this fails with `WouldBlock` if it is already zero) and set the new value
+		let _ = (&*eventfd).read(&mut [0; 8]);
+		if counter > 0 {
+			let _ = (&*eventfd).write(&counter.to_ne_bytes());
+		}
+		true
+	}
+}
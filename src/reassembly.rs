@@ -0,0 +1,254 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{
+	cmp::{ min, max }, collections::BTreeMap, ops::Range,
+	io::{ Read, Result as IoResult }
+};
+
+
+/// A receive buffer that reassembles segments which arrive out of order
+///
+/// The segments are addressed by their absolute offset within the stream. Only the contiguous
+/// prefix is readable; the segments after the first hole are stored separately until the hole is
+/// filled. The limit bounds the reassembly window (i.e. only bytes before
+/// `consumed_total() + limit` are accepted).
+#[derive(Clone, Debug)]
+pub struct ReassemblyQueue {
+	queue: SliceQueue<u8>,
+	pending: BTreeMap<u64, Vec<u8>>,
+	limit: usize
+}
+impl ReassemblyQueue {
+	/// Creates a new `ReassemblyQueue`
+	///
+	/// Returns __the new `ReassemblyQueue`__
+	pub fn new() -> Self {
+		ReassemblyQueue{ queue: SliceQueue::new(), pending: BTreeMap::new(), limit: usize::MAX }
+	}
+	/// Creates a new `ReassemblyQueue` with a predefined limit (the default limit is `usize::MAX`)
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The size of the reassembly window
+	///
+	/// Returns __the new `ReassemblyQueue`__
+	pub fn with_limit(limit: usize) -> Self {
+		assert!(limit > 0, "`limit` is `0`");
+		ReassemblyQueue{ queue: SliceQueue::new(), pending: BTreeMap::new(), limit }
+	}
+	
+	
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `insert`-calls; bytes that have already been
+	/// accepted are not discarded._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The size of the reassembly window
+	pub fn set_limit(&mut self, limit: usize) {
+		assert!(limit > 0, "`limit` is `0`");
+		self.limit = limit
+	}
+	
+	/// The total amount of bytes consumed (this is also the absolute offset of the first readable
+	/// byte)
+	///
+	/// Returns __the total amount of bytes consumed__
+	pub fn consumed_total(&self) -> u64 {
+		self.queue.consumed_total()
+	}
+	/// The end of the contiguous prefix (this is also the absolute offset of the first hole)
+	///
+	/// Returns __the absolute offset after the last readable byte__
+	pub fn contiguous_end(&self) -> u64 {
+		self.queue.pushed_total()
+	}
+	/// The end of the reassembly window
+	///
+	/// Returns __the absolute offset of the first byte that would be rejected__
+	pub fn window_end(&self) -> u64 {
+		self.consumed_total().saturating_add(self.limit as u64)
+	}
+	/// The amount of bytes that are stored after the first hole
+	///
+	/// Returns __the amount of bytes that are not readable yet__
+	pub fn pending_len(&self) -> usize {
+		self.pending.values().map(|segment| segment.len()).sum()
+	}
+	/// The holes between the contiguous prefix and the last stored segment
+	///
+	/// Returns __the absolute ranges of the missing bytes__
+	pub fn holes(&self) -> Vec<Range<u64>> {
+		let mut position = self.contiguous_end();
+		self.pending.iter().map(|(start, segment)| {
+			let hole = position .. *start;
+			position = start + segment.len() as u64;
+			hole
+		}).collect()
+	}
+	
+	
+	/// Inserts `segment` at the absolute `offset`
+	///
+	/// _Info: Bytes that have already been received are ignored; overlapping segments are merged._
+	///
+	/// Parameters:
+	///  - `offset`: The absolute offset of the segment within the stream
+	///  - `segment`: The segment to insert
+	///
+	/// Returns either __`Ok(())`__ if the segment was inserted completely or __`Err(window_end)`__
+	/// if the bytes at or after `window_end` were discarded because they are outside of the
+	/// reassembly window
+	pub fn insert(&mut self, offset: u64, segment: &[u8]) -> Result<(), u64> {
+		let (end, window_end) = (offset.saturating_add(segment.len() as u64), self.window_end());
+		
+		// Trim the bytes that have already been received or are outside of the window
+		let (start, trimmed_end) = (max(offset, self.contiguous_end()), min(end, window_end));
+		if start < trimmed_end {
+			self.store(start, &segment[(start - offset) as usize .. (trimmed_end - offset) as usize]);
+			self.advance();
+		}
+		
+		if end <= window_end { Ok(()) }
+			else { Err(window_end) }
+	}
+	
+	/// Stores `data` as pending segment and merges it with all overlapping or adjacent segments
+	///
+	/// _Info: The lowest touching segment is extended in place, so growing a segment only copies
+	/// the new bytes._
+	fn store(&mut self, start: u64, data: &[u8]) {
+		let end = start + data.len() as u64;
+		
+		// Collect the starts of the segments that overlap or touch the new segment (in ascending order)
+		let mut touching: Vec<u64> = self.pending.range(..=end).rev()
+			.take_while(|(s, segment)| *s + segment.len() as u64 >= start)
+			.map(|(s, _)| *s)
+			.collect();
+		touching.reverse();
+		
+		// Take the lowest touching segment or store the new segment if nothing touches it
+		let (mut merged_start, mut merged) = match touching.first() {
+			Some(s) => (*s, self.pending.remove(s).unwrap_or_else(|| unreachable!("The segment exists"))),
+			None => {
+				self.pending.insert(start, data.to_vec());
+				return
+			}
+		};
+		
+		// Add the new bytes before and after it (the bytes that have been stored first are kept)
+		if start < merged_start {
+			merged.splice(0..0, data[..(merged_start - start) as usize].iter().cloned());
+			merged_start = start;
+		}
+		let merged_end = merged_start + merged.len() as u64;
+		if end > merged_end { merged.extend_from_slice(&data[(merged_end - start) as usize ..]); }
+		
+		// Merge the following segments (which overwrite the new bytes because they were stored first)
+		for s in touching.into_iter().skip(1) {
+			let segment = self.pending.remove(&s).unwrap_or_else(|| unreachable!("The segment exists"));
+			let offset = (s - merged_start) as usize;
+			let overlap = min(segment.len(), merged.len() - offset);
+			merged[offset..][..overlap].copy_from_slice(&segment[..overlap]);
+			merged.extend_from_slice(&segment[overlap..]);
+		}
+		self.pending.insert(merged_start, merged);
+	}
+	/// Moves the first pending segment into the contiguous prefix if it starts at its end
+	fn advance(&mut self) {
+		if let Some(segment) = self.pending.remove(&self.contiguous_end()) {
+			self.queue.push_n(segment).unwrap_or_else(|_| unreachable!("The queue is unlimited"));
+		}
+	}
+}
+impl ReadableSliceQueue<u8> for ReassemblyQueue {
+	/// The amount of readable bytes (i.e. the length of the contiguous prefix)
+	///
+	/// Returns __the amount of bytes that can be read from `self`__
+	fn len(&self) -> usize {
+		self.queue.len()
+	}
+	/// Checks if there are __no__ readable bytes
+	///
+	/// Returns either __`true`__ if the contiguous prefix is empty or __`false`__ otherwise
+	fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+	/// The size of the reassembly window
+	///
+	/// Returns __the current size-limit of `self`__
+	fn limit(&self) -> usize {
+		self.limit
+	}
+	
+	/// Take a look at the first readable byte __without__ consuming it
+	///
+	/// Returns either _`Some(byte_ref)`_ if we have a readable byte or _`None`_ otherwise
+	fn peek(&self) -> Option<&u8> {
+		self.queue.peek()
+	}
+	/// Take a look at the first `n` readable bytes __without__ consuming them
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to peek at
+	///
+	/// Returns either __`Ok(byte_refs)`__ if there were `n` bytes avaliable to peek at or
+	/// __`Err(byte_refs)`__ if less bytes were available
+	fn peek_n(&self, n: usize) -> Result<&[u8], &[u8]> {
+		self.queue.peek_n(n)
+	}
+	
+	/// Consumes the first readable byte and returns it
+	///
+	/// Returns either __`Ok(byte)`__ if there was a byte to consume or __`Err(())`__ otherwise
+	fn pop(&mut self) -> Result<u8, ()> {
+		self.queue.pop()
+	}
+	/// Consumes the first `n` readable bytes and returns them
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to consume
+	///
+	/// Returns either __`Ok(bytes)`__ if there were `n` bytes avaliable to consume or
+	/// __`Err(bytes)`__ if less bytes were available
+	fn pop_n(&mut self, n: usize) -> Result<Vec<u8>, Vec<u8>> {
+		self.queue.pop_n(n)
+	}
+	/// Consumes the first `dst.len()` readable bytes and moves them into `dst`
+	///
+	/// Parameters:
+	///  - `dst`: The target to move the bytes into
+	///
+	/// Returns either __`Ok(())`__ if `dst` was filled completely or __`Err(byte_count)`__ if only
+	/// `byte_count` bytes were moved
+	fn pop_into(&mut self, dst: &mut[u8]) -> Result<(), usize> {
+		self.queue.pop_into(dst)
+	}
+	
+	/// Discards the first `n` readable bytes
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to discard
+	///
+	/// Returns either __`Ok(())`__ if `n` bytes were discarded or __`Err(byte_count)`__ if only
+	/// `byte_count` bytes were discarded
+	fn drop_n(&mut self, n: usize) -> Result<(), usize> {
+		self.queue.drop_n(n)
+	}
+}
+impl Read for ReassemblyQueue {
+	/// Pull some bytes from the contiguous prefix into the specified buffer, returning how many
+	/// bytes were read.
+	///
+	/// __This call never fails; the result is only used for trait-compatibility__
+	fn read(&mut self, buf: &mut[u8]) -> IoResult<usize> {
+		self.queue.read(buf)
+	}
+}
+impl Default for ReassemblyQueue {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,105 @@
+use super::SharedSliceQueue;
+use std::{
+	sync::{ Arc, Mutex, Condvar, PoisonError },
+	time::{ Duration, Instant }
+};
+
+
+/// The condition to wait for
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Interest {
+	/// The queue contains at least `n` elements
+	Readable(usize),
+	/// At least `n` elements can be pushed into the queue
+	Writeable(usize)
+}
+
+
+/// A signal that is raised by the queues if they are modified
+#[derive(Debug, Default)]
+pub struct Signal {
+	raised: Mutex<bool>,
+	condvar: Condvar
+}
+impl Signal {
+	/// Raises the signal and wakes the waiting thread
+	pub fn raise(&self) {
+		*self.raised.lock().unwrap_or_else(PoisonError::into_inner) = true;
+		self.condvar.notify_all()
+	}
+	/// Resets the signal
+	pub(crate) fn reset(&self) {
+		*self.raised.lock().unwrap_or_else(PoisonError::into_inner) = false
+	}
+	/// Waits until the signal is raised or `deadline` is reached
+	///
+	/// Returns either __`true`__ if the signal was raised or __`false`__ if the deadline was reached
+	pub(crate) fn wait(&self, deadline: Option<Instant>) -> bool {
+		let mut raised = self.raised.lock().unwrap_or_else(PoisonError::into_inner);
+		while !*raised {
+			raised = match deadline.map(|deadline| deadline.saturating_duration_since(Instant::now())) {
+				None => self.condvar.wait(raised).unwrap_or_else(PoisonError::into_inner),
+				Some(timeout) if timeout == Duration::from_secs(0) => return false,
+				Some(timeout) => self.condvar.wait_timeout(raised, timeout).unwrap_or_else(PoisonError::into_inner).0
+			};
+		}
+		true
+	}
+}
+
+
+/// The sealed part of `Selectable`
+mod sealed {
+	use super::{ Interest, Signal };
+	use std::sync::Arc;
+	
+	pub trait Sealed {
+		/// Checks if `interest` is satisfied and registers `signal` otherwise
+		fn check_or_register(&self, interest: Interest, signal: &Arc<Signal>) -> bool;
+	}
+}
+/// A queue that can be used with `select` (this trait is implemented by `SharedSliceQueue<T>`)
+pub trait Selectable: sealed::Sealed {}
+impl<T> sealed::Sealed for SharedSliceQueue<T> {
+	fn check_or_register(&self, interest: Interest, signal: &Arc<Signal>) -> bool {
+		SharedSliceQueue::check_or_register(self, interest, signal)
+	}
+}
+impl<T> Selectable for SharedSliceQueue<T> {}
+
+
+/// Waits until any of the `queues` satisfies its interest
+///
+/// _Info: The queues may have different element types. The readiness is only a snapshot; another
+/// thread may change the queue before you access it._
+///
+/// Parameters:
+///  - `queues`: The queues and the conditions to wait for
+///  - `timeout`: The maximum duration to wait (`None` waits forever; `Some(Duration::from_secs(0))`
+///    only checks the queues without waiting)
+///
+/// Returns either __`Some(index)`__ with the index of the first ready queue in `queues` or
+/// __`None`__ if the timeout has expired
+///
+/// Example:
+/// ```
+/// # extern crate slice_queue;
+/// # use slice_queue::*;
+/// # use std::time::Duration;
+/// let (control, data) = (SharedSliceQueue::<u8>::new(), SharedSliceQueue::<u32>::with_limit(4));
+/// data.push_from(&[1, 2, 3]).unwrap();
+///
+/// let queues: [(&dyn Selectable, Interest); 2] = [(&control, Interest::Readable(1)), (&data, Interest::Readable(3))];
+/// assert_eq!(select(&queues, Some(Duration::from_millis(10))), Some(1));
+/// ```
+pub fn select(queues: &[(&dyn Selectable, Interest)], timeout: Option<Duration>) -> Option<usize> {
+	let (signal, deadline) = (Arc::new(Signal::default()), timeout.map(|timeout| Instant::now() + timeout));
+	loop {
+		// Reset the signal before checking so that no modification is missed
+		signal.reset();
+		if let Some(index) = queues.iter().position(|(queue, interest)| queue.check_or_register(*interest, &signal)) {
+			return Some(index)
+		}
+		if !signal.wait(deadline) { return None }
+	}
+}
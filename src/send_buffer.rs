@@ -0,0 +1,182 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{ cmp::min, io::{ Write, Result as IoResult } };
+
+
+/// A send buffer that keeps sent data until it is acknowledged
+///
+/// The data is addressed by its absolute offset within the stream. The buffer tracks two
+/// positions: the send position (everything before it has been sent at least once) and the acked
+/// position (everything before it has been acknowledged and released).
+#[derive(Clone, Debug)]
+pub struct SendBuffer {
+	queue: SliceQueue<u8>,
+	sent: u64
+}
+impl SendBuffer {
+	/// Creates a new `SendBuffer`
+	///
+	/// Returns __the new `SendBuffer`__
+	pub fn new() -> Self {
+		SendBuffer{ queue: SliceQueue::new(), sent: 0 }
+	}
+	/// Creates a new `SendBuffer` with a predefined limit (the default limit is `usize::MAX`)
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of unacknowledged bytes that can be stored by `self`
+	///
+	/// Returns __the new `SendBuffer`__
+	pub fn with_limit(limit: usize) -> Self {
+		SendBuffer{ queue: SliceQueue::with_limit(limit), sent: 0 }
+	}
+	
+	
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of unacknowledged bytes that can be stored by `self`
+	pub fn set_limit(&mut self, limit: usize) {
+		self.queue.set_limit(limit)
+	}
+	/// The current limit
+	///
+	/// Returns __the maximum amount of unacknowledged bytes that can be stored by `self`__
+	pub fn limit(&self) -> usize {
+		self.queue.limit()
+	}
+	
+	
+	/// The absolute acked position
+	///
+	/// Returns __the absolute offset of the first unacknowledged byte__
+	pub fn acked(&self) -> u64 {
+		self.queue.consumed_total()
+	}
+	/// The absolute send position
+	///
+	/// Returns __the absolute offset of the first byte that has not been sent yet__
+	pub fn sent(&self) -> u64 {
+		self.sent
+	}
+	/// The absolute end position
+	///
+	/// Returns __the absolute offset of the next byte to push__
+	pub fn pushed(&self) -> u64 {
+		self.queue.pushed_total()
+	}
+	
+	/// The amount of unacknowledged bytes (including the bytes that have not been sent yet)
+	///
+	/// Returns __the amount of bytes stored in `self`__
+	pub fn len(&self) -> usize {
+		self.queue.len()
+	}
+	/// Checks if there are __no__ unacknowledged bytes
+	///
+	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
+	pub fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+	/// The amount of bytes that have been sent but not acknowledged yet
+	///
+	/// Returns __the amount of bytes in flight__
+	pub fn in_flight(&self) -> usize {
+		(self.sent - self.acked()) as usize
+	}
+	/// The amount of bytes that have not been sent yet
+	///
+	/// Returns __the amount of unsent bytes__
+	pub fn unsent(&self) -> usize {
+		(self.pushed() - self.sent) as usize
+	}
+	/// The amount of bytes that can be pushed until the limit is reached
+	///
+	/// Returns __the amount of bytes remaining until the limit is reached__
+	pub fn remaining(&self) -> usize {
+		self.queue.remaining()
+	}
+	
+	
+	/// Appends the bytes in `src` at the end
+	///
+	/// Parameters:
+	///  - `src`: A slice containing the bytes to append
+	///
+	/// Returns either __`Ok(())`__ if `src` was appended completely or
+	/// __`Err(appended_count)`__ if `src` was only appended partially because the limit would have
+	/// been exceeded
+	pub fn push(&mut self, src: &[u8]) -> Result<(), usize> {
+		self.queue.push_from(src)
+	}
+	
+	/// Marks up to `max_len` unsent bytes as sent and returns them
+	///
+	/// Parameters:
+	///  - `max_len`: The maximum amount of bytes to send
+	///
+	/// Returns __the absolute offset of the bytes and the bytes__ (which are empty if there are no
+	/// unsent bytes)
+	pub fn send(&mut self, max_len: usize) -> (u64, &[u8]) {
+		let (offset, len) = (self.sent, min(max_len, self.unsent()));
+		self.sent += len as u64;
+		(offset, self.queue.peek_abs(offset .. self.sent).unwrap_or_else(|| unreachable!("The bytes are buffered")))
+	}
+	/// Take a look at already sent bytes (e.g. to retransmit them)
+	///
+	/// Parameters:
+	///  - `offset`: The absolute offset of the first byte
+	///  - `len`: The amount of bytes to peek at
+	///
+	/// Returns either __`Some(bytes)`__ or __`None`__ if some of the bytes have already been
+	/// acknowledged or have not been sent yet
+	pub fn peek_from_abs(&self, offset: u64, len: usize) -> Option<&[u8]> {
+		match offset.checked_add(len as u64) {
+			Some(end) if end <= self.sent => self.queue.peek_abs(offset .. end),
+			_ => None
+		}
+	}
+	/// Resets the send position to the acked position so that all bytes in flight are sent again
+	pub fn rewind(&mut self) {
+		self.sent = self.acked()
+	}
+	
+	/// Acknowledges and releases all bytes before the absolute offset `up_to`
+	///
+	/// _Info: Acknowledging an offset that has already been acknowledged does nothing._
+	///
+	/// Parameters:
+	///  - `up_to`: The absolute offset of the first byte that has not been acknowledged
+	///
+	/// Returns either __`Ok(released_count)`__ or __`Err(up_to)`__ if `up_to` is beyond the send
+	/// position (nothing is released in this case)
+	pub fn ack(&mut self, up_to: u64) -> Result<usize, u64> {
+		if up_to > self.sent { return Err(up_to) }
+		
+		let released = up_to.saturating_sub(self.acked()) as usize;
+		self.queue.drop_n(released).unwrap_or_else(|_| unreachable!("The bytes are buffered"));
+		Ok(released)
+	}
+}
+impl Write for SendBuffer {
+	/// Write a buffer into this object, returning how many bytes were written.
+	///
+	/// __This call never fails; the result is only used for trait-compatibility__
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		self.queue.write(buf)
+	}
+	/// __This call does nothing (and thus never fails); it is only provided for
+	/// trait-compatibility__
+	fn flush(&mut self) -> IoResult<()> {
+		Ok(())
+	}
+}
+impl Default for SendBuffer {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,359 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue, select::{ Interest, Signal } };
+use std::{
+	sync::{ Arc, Weak, Mutex, MutexGuard, PoisonError },
+	time::{ Duration, Instant }
+};
+#[cfg(target_os = "linux")]
+use super::readiness::{ Readiness, Notifier };
+#[cfg(target_os = "linux")]
+use std::io::Result as IoResult;
+
+
+/// The state that is shared between all handles
+#[derive(Debug)]
+struct State<T> {
+	queue: SliceQueue<T>,
+	signals: Vec<Weak<Signal>>,
+	#[cfg(target_os = "linux")]
+	notifiers: Vec<Notifier>
+}
+
+
+/// A thread-safe `SliceQueue` that can be shared between threads
+///
+/// Cloning a `SharedSliceQueue` creates a new handle to the same queue. All calls lock the queue for
+/// their duration, so every call is atomic; this also makes the queue usable with multiple producers
+/// and multiple consumers. The `*_batch`-calls are all-or-nothing, so a batch is never split.
+#[derive(Debug)]
+pub struct SharedSliceQueue<T> {
+	state: Arc<Mutex<State<T>>>
+}
+impl<T> SharedSliceQueue<T> {
+	/// Creates a new `SharedSliceQueue`
+	///
+	/// Returns __the new `SharedSliceQueue`__
+	pub fn new() -> Self {
+		Self::from(SliceQueue::new())
+	}
+	/// Creates a new `SharedSliceQueue` with a predefined limit (the default limit is `usize::MAX`)
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that can be stored by `self`
+	///
+	/// Returns __the new `SharedSliceQueue`__
+	pub fn with_limit(limit: usize) -> Self {
+		Self::from(SliceQueue::with_limit(limit))
+	}
+	
+	
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that can be stored by `self`
+	pub fn set_limit(&self, limit: usize) {
+		self.modify(|queue| queue.set_limit(limit))
+	}
+	/// The current limit
+	///
+	/// Returns __the maximum amount of elements that can be stored by `self`__
+	pub fn limit(&self) -> usize {
+		self.lock().queue.limit()
+	}
+	
+	/// The amount of elements stored
+	///
+	/// Returns __the amount of elements stored in `self`__
+	pub fn len(&self) -> usize {
+		self.lock().queue.len()
+	}
+	/// Checks if there are __no__ elements stored
+	///
+	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
+	pub fn is_empty(&self) -> bool {
+		self.lock().queue.is_empty()
+	}
+	/// The amount of elements that can be pushed until the limit is reached
+	///
+	/// Returns __the amount of elements remaining until the limit is reached__
+	pub fn remaining(&self) -> usize {
+		self.lock().queue.remaining()
+	}
+	
+	
+	/// Appends `element` at the end
+	///
+	/// Parameters:
+	///  - `element`: The element to append at the end
+	///
+	/// Returns either __`Ok(())`__ if the element was pushed successfully or __`Err(element)`__ if
+	/// `element` was not appended because the limit would have been exceeded
+	pub fn push(&self, element: T) -> Result<(), T> {
+		self.modify(|queue| queue.push(element))
+	}
+	/// Appends `n` at the end
+	///
+	/// Parameters:
+	///  - `n`: The n elements to append at the end
+	///
+	/// Returns either __`Ok(())`__ if `n` was appended completely or __`Err(remaining_elements)`__
+	/// if `n` was only appended partially because the limit would have been exceeded
+	pub fn push_n(&self, n: Vec<T>) -> Result<(), Vec<T>> {
+		self.modify(|queue| queue.push_n(n))
+	}
+	
+	/// Consumes the first element and returns it
+	///
+	/// Returns either __`Some(element)`__ or __`None`__ if `self` is empty
+	pub fn pop(&self) -> Option<T> {
+		self.modify(|queue| queue.pop().ok())
+	}
+	/// Consumes the first `n` elements and returns them
+	///
+	/// Parameters:
+	///  - `n`: The amount of elements to consume
+	///
+	/// Returns either __`Ok(elements)`__ if there were `n` elements available to consume or
+	/// __`Err(elements)`__ if less elements were available
+	pub fn pop_n(&self, n: usize) -> Result<Vec<T>, Vec<T>> {
+		self.modify(|queue| queue.pop_n(n))
+	}
+	/// Consumes the first `dst.len()` elements and moves them into `dst`
+	///
+	/// Parameters:
+	///  - `dst`: The target to move the elements into
+	///
+	/// Returns either __`Ok(())`__ if `dst` was filled completely or __`Err(element_count)`__ if
+	/// only `element_count` elements were moved
+	pub fn pop_into(&self, dst: &mut[T]) -> Result<(), usize> {
+		self.modify(|queue| queue.pop_into(dst))
+	}
+	/// Discards the first `n` elements
+	///
+	/// Parameters:
+	///  - `n`: The amount of elements to discard
+	///
+	/// Returns either __`Ok(())`__ if `n` elements were discarded or __`Err(element_count)`__ if
+	/// only `element_count` elements were discarded
+	pub fn drop_n(&self, n: usize) -> Result<(), usize> {
+		self.modify(|queue| queue.drop_n(n))
+	}
+	
+	
+	/// Appends all elements of `batch` at the end or nothing at all
+	///
+	/// Parameters:
+	///  - `batch`: The elements to append at the end
+	///
+	/// Returns either __`Ok(())`__ if `batch` was appended or __`Err(batch)`__ if `batch` was not
+	/// appended because the limit would have been exceeded
+	pub fn push_batch(&self, batch: Vec<T>) -> Result<(), Vec<T>> {
+		let mut state = self.lock();
+		if batch.len() > state.queue.remaining() { return Err(batch) }
+		
+		state.queue.push_n(batch).unwrap_or_else(|_| unreachable!("The batch fits into the queue"));
+		state.notify();
+		Ok(())
+	}
+	/// Appends all elements of `batch` at the end and waits until there is enough space if necessary
+	///
+	/// Parameters:
+	///  - `batch`: The elements to append at the end
+	///  - `timeout`: The maximum duration to wait (`None` waits forever)
+	///
+	/// Returns either __`Ok(())`__ if `batch` was appended or __`Err(batch)`__ if the timeout has
+	/// expired or `batch` is (or becomes) larger than the limit
+	pub fn push_batch_blocking(&self, batch: Vec<T>, timeout: Option<Duration>) -> Result<(), Vec<T>> {
+		let (interest, mut batch) = (Interest::Writeable(batch.len()), Some(batch));
+		let pushed = self.retry(interest, timeout, || {
+			let to_push = batch.take().unwrap_or_else(|| unreachable!("The batch has not been pushed yet"));
+			
+			// Give up if the batch can never fit (the limit may be lowered while we are waiting)
+			if to_push.len() > self.limit() {
+				batch = Some(to_push);
+				return Some(false)
+			}
+			self.push_batch(to_push).map_err(|to_push| batch = Some(to_push)).ok().map(|_| true)
+		});
+		match pushed {
+			Some(true) => Ok(()),
+			_ => Err(batch.unwrap_or_else(|| unreachable!("The batch has not been pushed")))
+		}
+	}
+	/// Consumes exactly `n` elements or nothing at all
+	///
+	/// Parameters:
+	///  - `n`: The amount of elements to consume
+	///
+	/// Returns either __`Some(batch)`__ or __`None`__ if less than `n` elements are available
+	pub fn pop_batch(&self, n: usize) -> Option<Vec<T>> {
+		let mut state = self.lock();
+		if n > state.queue.len() { return None }
+		
+		let batch = state.queue.pop_n(n).unwrap_or_else(|_| unreachable!("The batch is available"));
+		state.notify();
+		Some(batch)
+	}
+	/// Consumes exactly `n` elements and waits until they are available if necessary
+	///
+	/// Parameters:
+	///  - `n`: The amount of elements to consume
+	///  - `timeout`: The maximum duration to wait (`None` waits forever)
+	///
+	/// Returns either __`Some(batch)`__ or __`None`__ if the timeout has expired or `n` is (or
+	/// becomes) larger than the limit
+	pub fn pop_batch_blocking(&self, n: usize, timeout: Option<Duration>) -> Option<Vec<T>> {
+		// Give up if the batch can never be available (the limit may be lowered while we are waiting)
+		self.retry(Interest::Readable(n), timeout, || {
+			if n > self.limit() { Some(None) }
+				else { self.pop_batch(n).map(Some) }
+		}).and_then(|batch| batch)
+	}
+	
+	
+	/// Creates a pollable readiness handle that is readable if `self` is not empty and writeable if
+	/// at least `threshold` elements can be pushed
+	///
+	/// Parameters:
+	///  - `threshold`: The amount of elements that must fit into `self` for the handle to be
+	///    writeable
+	///
+	/// Returns either __`Ok(readiness)`__ or the I/O error that occurred while creating the eventfd
+	#[cfg(target_os = "linux")]
+	pub fn readiness(&self, threshold: usize) -> IoResult<Readiness> {
+		let mut state = self.lock();
+		let readiness = Readiness::new(threshold, state.queue.len(), state.queue.remaining())?;
+		state.notifiers.push(readiness.notifier());
+		Ok(readiness)
+	}
+	
+	
+	/// Checks if `interest` is satisfied and registers `signal` to be raised on the next modification
+	/// otherwise
+	pub(crate) fn check_or_register(&self, interest: Interest, signal: &Arc<Signal>) -> bool {
+		let mut state = self.lock();
+		let ready = match interest {
+			Interest::Readable(n) => state.queue.len() >= n,
+			Interest::Writeable(n) => state.queue.remaining() >= n
+		};
+		
+		// Register the signal once and remove the signals of finished waits
+		if !ready {
+			state.signals.retain(|registered| registered.strong_count() > 0);
+			if !state.signals.iter().any(|registered| registered.as_ptr() == Arc::as_ptr(signal)) {
+				state.signals.push(Arc::downgrade(signal));
+			}
+		}
+		ready
+	}
+	
+	
+	/// Calls `f` until it succeeds and waits for the next modification in between if `interest` is
+	/// not satisfied
+	///
+	/// _Info: `f` is called after every modification (not only once `interest` is satisfied), so it
+	/// can give up if `interest` can never be satisfied (e.g. because the limit has been lowered)._
+	///
+	/// Returns either __`Some(result)`__ or __`None`__ if the timeout has expired
+	fn retry<R>(&self, interest: Interest, timeout: Option<Duration>, mut f: impl FnMut() -> Option<R>) -> Option<R> {
+		let (signal, deadline) = (Arc::new(Signal::default()), timeout.map(|timeout| Instant::now() + timeout));
+		loop {
+			// Reset the signal before retrying so that no modification is missed (another consumer or
+			// producer may win the race between the wakeup and the retry)
+			signal.reset();
+			if let Some(result) = f() { return Some(result) }
+			if !self.check_or_register(interest, &signal) && !signal.wait(deadline) { return None }
+		}
+	}
+	/// Locks the shared state (a poisoned lock is ignored because every call leaves the queue in a
+	/// consistent state)
+	fn lock(&self) -> MutexGuard<'_, State<T>> {
+		self.state.lock().unwrap_or_else(PoisonError::into_inner)
+	}
+	/// Calls `f` with the locked queue and updates the readiness handles afterwards
+	fn modify<R>(&self, f: impl FnOnce(&mut SliceQueue<T>) -> R) -> R {
+		let mut state = self.lock();
+		let result = f(&mut state.queue);
+		state.notify();
+		result
+	}
+}
+impl<T: Clone> SharedSliceQueue<T> {
+	/// Clones and appends the elements in `src` at the end
+	///
+	/// Parameters:
+	///  - `src`: A slice containing the elements to clone and append
+	///
+	/// Returns either __`Ok(())`__ if `src` was appended completely or __`Err(appended_count)`__ if
+	/// `src` was only appended partially because the limit would have been exceeded
+	pub fn push_from(&self, src: &[T]) -> Result<(), usize> {
+		self.modify(|queue| queue.push_from(src))
+	}
+	/// Clones and appends all elements of `batch` at the end or nothing at all
+	///
+	/// _Info: The elements are cloned before the queue is locked._
+	///
+	/// Parameters:
+	///  - `batch`: A slice containing the elements to clone and append
+	///
+	/// Returns either __`Ok(())`__ if `batch` was appended or __`Err(batch.len())`__ if `batch` was
+	/// not appended because the limit would have been exceeded
+	pub fn push_batch_from(&self, batch: &[T]) -> Result<(), usize> {
+		self.push_batch(batch.to_vec()).map_err(|batch| batch.len())
+	}
+	/// Clones the first `n` elements __without__ consuming them
+	///
+	/// Parameters:
+	///  - `n`: The amount of elements to clone
+	///
+	/// Returns either __`Ok(elements)`__ if there were `n` elements available or
+	/// __`Err(elements)`__ if less elements were available
+	pub fn peek_n(&self, n: usize) -> Result<Vec<T>, Vec<T>> {
+		match self.lock().queue.peek_n(n) {
+			Ok(elements) => Ok(elements.to_vec()),
+			Err(elements) => Err(elements.to_vec())
+		}
+	}
+}
+impl<T> State<T> {
+	/// Raises all signals and updates all readiness handles (and removes the dropped ones)
+	fn notify(&mut self) {
+		self.signals.drain(..).filter_map(|signal| signal.upgrade()).for_each(|signal| signal.raise());
+		self.update_readiness();
+	}
+	/// Updates all readiness handles and removes the dropped ones
+	#[cfg(target_os = "linux")]
+	fn update_readiness(&mut self) {
+		let (len, remaining) = (self.queue.len(), self.queue.remaining());
+		self.notifiers.retain(|notifier| notifier.update(len, remaining));
+	}
+	/// Does nothing because there are no readiness handles on this platform
+	#[cfg(not(target_os = "linux"))]
+	fn update_readiness(&mut self) {}
+}
+impl<T> From<SliceQueue<T>> for SharedSliceQueue<T> {
+	fn from(queue: SliceQueue<T>) -> Self {
+		let state = State {
+			queue, signals: Vec::new(),
+			#[cfg(target_os = "linux")]
+			notifiers: Vec::new()
+		};
+		SharedSliceQueue{ state: Arc::new(Mutex::new(state)) }
+	}
+}
+impl<T> Clone for SharedSliceQueue<T> {
+	/// Creates a new handle to the same queue
+	fn clone(&self) -> Self {
+		SharedSliceQueue{ state: self.state.clone() }
+	}
+}
+impl<T> Default for SharedSliceQueue<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
@@ -0,0 +1,435 @@
+use super::{ ReadableSliceQueue, WriteableSliceQueue };
+use std::{
+	cmp::min, ptr, slice, fs::{ File, OpenOptions }, path::Path,
+	os::{ raw::{ c_void, c_long }, unix::{ fs::FileExt, io::AsRawFd } },
+	sync::atomic::{ AtomicU64, Ordering },
+	io::{ self, Read, Write, Result as IoResult }
+};
+
+
+/// The raw Linux memory mapping API
+mod sys {
+	use std::os::raw::{ c_void, c_int, c_long };
+	
+	pub const PROT_NONE: c_int = 0;
+	pub const PROT_READ: c_int = 1;
+	pub const PROT_WRITE: c_int = 2;
+	pub const MAP_SHARED: c_int = 0x01;
+	pub const MAP_PRIVATE: c_int = 0x02;
+	pub const MAP_FIXED: c_int = 0x10;
+	#[cfg(any(target_arch = "mips", target_arch = "mips64"))]
+	pub const MAP_ANONYMOUS: c_int = 0x800;
+	#[cfg(not(any(target_arch = "mips", target_arch = "mips64")))]
+	pub const MAP_ANONYMOUS: c_int = 0x20;
+	pub const SC_PAGESIZE: c_int = 30;
+	
+	extern "C" {
+		pub fn mmap(addr: *mut c_void, len: usize, prot: c_int, flags: c_int, fd: c_int, offset: c_long) -> *mut c_void;
+		pub fn munmap(addr: *mut c_void, len: usize) -> c_int;
+		pub fn sysconf(name: c_int) -> c_long;
+	}
+}
+
+
+/// The magic number at the beginning of the header page
+const MAGIC: u64 = 0x534c_5153_484d_0001;
+
+
+/// The header page that is shared between both processes
+///
+/// _Info: `head` is only written by the consumer and `tail` is only written by the producer; they
+/// are placed on separate cache lines to avoid false sharing._
+#[repr(C)]
+struct Header {
+	magic: AtomicU64,
+	capacity: AtomicU64,
+	_padding0: [u8; 48],
+	head: AtomicU64,
+	_padding1: [u8; 56],
+	tail: AtomicU64
+}
+
+
+/// Returns the page size
+fn page_size() -> usize {
+	match unsafe{ sys::sysconf(sys::SC_PAGESIZE) } {
+		size if size > 0 => size as usize,
+		_ => 4096
+	}
+}
+/// Creates an `InvalidData`-error with `message`
+fn invalid_data(message: &'static str) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+
+/// A single-producer/single-consumer byte ring that lives in a shared memory region
+///
+/// The region consists of a header page (which contains the head and tail positions as atomics)
+/// followed by the ring data. The data is mapped twice in a row, so that every readable or writeable
+/// range is contiguous. One process creates the region using `create`/`create_file` and another
+/// process attaches to it using `open`/`open_file` (e.g. using a path in `/dev/shm` or a `memfd`
+/// that is passed to the other process).
+///
+/// __Warning: Exactly one process (or thread) must push and exactly one must consume; pushing or
+/// consuming concurrently from two handles corrupts the data (but never the memory outside of the
+/// region)__
+///
+/// _Info: The bytes in the shared region can be modified by the other process at any time, so they
+/// are always copied using volatile reads and writes and never borrowed. Therefore `peek` and
+/// `peek_n` cannot return the bytes; use `peek_into` to copy them without consuming them (or
+/// `peek_raw` if the other process is trusted)._
+#[derive(Debug)]
+pub struct ShmQueue {
+	file: File,
+	mapping: *mut u8,
+	mapping_len: usize,
+	page_size: usize,
+	capacity: usize
+}
+impl ShmQueue {
+	/// Creates a new shared-memory region at `path` and attaches to it
+	///
+	/// Parameters:
+	///  - `path`: The path of the backing file (which must not exist yet; use a path in `/dev/shm`
+	///    to avoid disk I/O)
+	///  - `capacity`: The minimum capacity in bytes (which is rounded up to the page size)
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error that occurred
+	pub fn create(path: impl AsRef<Path>, capacity: usize) -> IoResult<Self> {
+		let file = OpenOptions::new().read(true).write(true).create_new(true).open(path)?;
+		Self::create_file(file, capacity)
+	}
+	/// Initializes a new shared-memory region in `file` (e.g. a `memfd`) and attaches to it
+	///
+	/// _Info: The previous contents of `file` are discarded._
+	///
+	/// Parameters:
+	///  - `file`: The backing file (which must be opened for reading and writing)
+	///  - `capacity`: The minimum capacity in bytes (which is rounded up to the page size)
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error that occurred
+	pub fn create_file(file: File, capacity: usize) -> IoResult<Self> {
+		let page_size = page_size();
+		let capacity = match capacity.max(1).checked_add(page_size - 1) {
+			Some(capacity) => capacity / page_size * page_size,
+			None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "The capacity is too large"))
+		};
+		
+		// Resize the file and initialize the header (the magic number is written last)
+		file.set_len(0)?;
+		file.set_len((page_size + capacity) as u64)?;
+		let queue = Self::map(file, page_size, capacity)?;
+		queue.header().capacity.store(capacity as u64, Ordering::Relaxed);
+		queue.header().magic.store(MAGIC, Ordering::Release);
+		Ok(queue)
+	}
+	/// Attaches to the existing shared-memory region at `path`
+	///
+	/// Parameters:
+	///  - `path`: The path of the backing file
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error (`InvalidData` if the file does not contain
+	/// an initialized region)
+	pub fn open(path: impl AsRef<Path>) -> IoResult<Self> {
+		let file = OpenOptions::new().read(true).write(true).open(path)?;
+		Self::open_file(file)
+	}
+	/// Attaches to the existing shared-memory region in `file`
+	///
+	/// _Info: The header is read from the beginning of `file`, regardless of the file's cursor._
+	///
+	/// Parameters:
+	///  - `file`: The backing file (which must be opened for reading and writing)
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error (`InvalidData` if the file does not contain
+	/// an initialized region)
+	pub fn open_file(file: File) -> IoResult<Self> {
+		let page_size = page_size();
+		let file_len = file.metadata()?.len();
+		
+		// Read and validate the header fields
+		let mut header = [0; 16];
+		file.read_exact_at(&mut header, 0).map_err(|_| invalid_data("The region is not initialized"))?;
+		let magic = u64::from_ne_bytes([header[0], header[1], header[2], header[3], header[4], header[5], header[6], header[7]]);
+		let capacity = u64::from_ne_bytes([header[8], header[9], header[10], header[11], header[12], header[13], header[14], header[15]]);
+		if magic != MAGIC { return Err(invalid_data("The region is not initialized")) }
+		if capacity == 0 || capacity % page_size as u64 != 0 || Some(file_len) != capacity.checked_add(page_size as u64) {
+			return Err(invalid_data("The region has an invalid capacity"))
+		}
+		
+		Self::map(file, page_size, capacity as usize)
+	}
+	/// Maps the header page and the data twice in a row
+	fn map(file: File, page_size: usize, capacity: usize) -> IoResult<Self> {
+		let mapping_len = capacity.checked_mul(2).and_then(|len| len.checked_add(page_size))
+			.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "The capacity is too large"))?;
+		unsafe {
+			// Reserve the address space
+			let mapping = sys::mmap(ptr::null_mut(), mapping_len, sys::PROT_NONE, sys::MAP_PRIVATE | sys::MAP_ANONYMOUS, -1, 0);
+			if mapping as isize == -1 { return Err(io::Error::last_os_error()) }
+			let queue = ShmQueue{ file, mapping: mapping as *mut u8, mapping_len, page_size, capacity };
+			
+			// Map the header page and the data and then map the data again directly after it
+			let (protection, flags) = (sys::PROT_READ | sys::PROT_WRITE, sys::MAP_SHARED | sys::MAP_FIXED);
+			let fd = queue.file.as_raw_fd();
+			let first = sys::mmap(mapping, page_size + capacity, protection, flags, fd, 0);
+			if first as isize == -1 { return Err(io::Error::last_os_error()) }
+			let mirror = queue.mapping.add(page_size + capacity) as *mut c_void;
+			let second = sys::mmap(mirror, capacity, protection, flags, fd, page_size as c_long);
+			if second as isize == -1 { return Err(io::Error::last_os_error()) }
+			Ok(queue)
+		}
+	}
+	
+	
+	/// The capacity of the ring
+	///
+	/// Returns __the maximum amount of bytes that can be stored in the region__
+	pub fn capacity(&self) -> usize {
+		self.capacity
+	}
+	/// The total amount of bytes consumed (by any handle)
+	///
+	/// Returns __the total amount of bytes consumed__
+	pub fn consumed_total(&self) -> u64 {
+		self.header().head.load(Ordering::Acquire)
+	}
+	/// The total amount of bytes pushed (by any handle)
+	///
+	/// Returns __the total amount of bytes pushed__
+	pub fn pushed_total(&self) -> u64 {
+		self.header().tail.load(Ordering::Acquire)
+	}
+	
+	
+	/// Copies the first `dst.len()` bytes into `dst` __without__ consuming them
+	///
+	/// Parameters:
+	///  - `dst`: The target to copy the bytes into
+	///
+	/// Returns either __`Ok(())`__ if `dst` was filled completely or __`Err(byte_count)`__ if only
+	/// `byte_count` bytes were available
+	pub fn peek_into(&self, dst: &mut[u8]) -> Result<(), usize> {
+		let (head, len) = self.readable();
+		let to_copy = min(len, dst.len());
+		self.copy_out(head, &mut dst[..to_copy]);
+		
+		if to_copy == dst.len() { Ok(()) }
+			else { Err(to_copy) }
+	}
+	/// Take a look at the first `n` bytes __without__ consuming or copying them
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to peek at
+	///
+	/// Returns either __`Ok(bytes)`__ if there were `n` bytes avaliable to peek at or
+	/// __`Err(bytes)`__ if less bytes were available
+	///
+	/// # Safety
+	/// The bytes live in the shared region. The other process must follow the protocol (i.e. it
+	/// must not modify the bytes until they have been consumed) for as long as the returned slice is
+	/// alive.
+	pub unsafe fn peek_raw(&self, n: usize) -> Result<&[u8], &[u8]> {
+		let (head, len) = self.readable();
+		let bytes = slice::from_raw_parts(self.data(head, min(n, len)), min(n, len));
+		if bytes.len() == n { Ok(bytes) }
+			else { Err(bytes) }
+	}
+	
+	
+	/// The shared header
+	fn header(&self) -> &Header {
+		unsafe{ &*(self.mapping as *const Header) }
+	}
+	/// The readable range (the length is clamped to the capacity, so that a misbehaving peer cannot
+	/// cause an access outside of the region)
+	fn readable(&self) -> (u64, usize) {
+		let head = self.header().head.load(Ordering::Relaxed);
+		let len = self.header().tail.load(Ordering::Acquire).wrapping_sub(head);
+		(head, min(len, self.capacity as u64) as usize)
+	}
+	/// The writeable range
+	fn writeable(&self) -> (u64, usize) {
+		let tail = self.header().tail.load(Ordering::Relaxed);
+		let len = tail.wrapping_sub(self.header().head.load(Ordering::Acquire));
+		(tail, self.capacity - min(len, self.capacity as u64) as usize)
+	}
+	/// The `len` bytes starting at the absolute `position` (which are contiguous thanks to the
+	/// mirrored mapping)
+	fn data(&self, position: u64, len: usize) -> *mut u8 {
+		debug_assert!(len <= self.capacity, "The range exceeds the ring");
+		unsafe{ self.mapping.add(self.page_size + (position % self.capacity as u64) as usize) }
+	}
+	
+	/// Copies the bytes starting at the absolute `position` into `dst` using volatile reads
+	fn copy_out(&self, position: u64, dst: &mut[u8]) {
+		let src = self.data(position, dst.len());
+		dst.iter_mut().enumerate().for_each(|(i, byte)| *byte = unsafe{ ptr::read_volatile(src.add(i)) });
+	}
+	/// Copies `src` to the absolute `position` using volatile writes
+	fn copy_in(&mut self, position: u64, src: &[u8]) {
+		let dst = self.data(position, src.len());
+		src.iter().enumerate().for_each(|(i, byte)| unsafe{ ptr::write_volatile(dst.add(i), *byte) });
+	}
+	
+	/// Marks `n` bytes as consumed
+	fn consume(&mut self, head: u64, n: usize) {
+		self.header().head.store(head.wrapping_add(n as u64), Ordering::Release)
+	}
+	/// Marks `n` bytes as pushed
+	fn commit(&mut self, tail: u64, n: usize) {
+		self.header().tail.store(tail.wrapping_add(n as u64), Ordering::Release)
+	}
+}
+impl ReadableSliceQueue<u8> for ShmQueue {
+	fn len(&self) -> usize {
+		self.readable().1
+	}
+	fn is_empty(&self) -> bool {
+		self.len() == 0
+	}
+	fn limit(&self) -> usize {
+		self.capacity
+	}
+	
+	/// The bytes in the shared region cannot be borrowed safely
+	///
+	/// Returns __`None`__ (use `peek_into` to copy the first byte instead)
+	fn peek(&self) -> Option<&u8> {
+		None
+	}
+	/// The bytes in the shared region cannot be borrowed safely
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to peek at
+	///
+	/// Returns __`Ok([])`__ if `n` is `0` or __`Err([])`__ otherwise (use `peek_into` to copy the
+	/// bytes instead)
+	fn peek_n(&self, n: usize) -> Result<&[u8], &[u8]> {
+		if n == 0 { Ok(&[]) }
+			else { Err(&[]) }
+	}
+	
+	fn pop(&mut self) -> Result<u8, ()> {
+		let mut byte = [0];
+		self.pop_into(&mut byte).map(|_| byte[0]).map_err(|_| ())
+	}
+	fn pop_n(&mut self, n: usize) -> Result<Vec<u8>, Vec<u8>> {
+		let mut bytes = vec![0; min(n, self.len())];
+		self.pop_into(&mut bytes).unwrap_or_else(|_| unreachable!("The bytes are available"));
+		
+		if bytes.len() == n { Ok(bytes) }
+			else { Err(bytes) }
+	}
+	fn pop_into(&mut self, dst: &mut[u8]) -> Result<(), usize> {
+		let (head, len) = self.readable();
+		let to_move = min(len, dst.len());
+		self.copy_out(head, &mut dst[..to_move]);
+		self.consume(head, to_move);
+		
+		if to_move == dst.len() { Ok(()) }
+			else { Err(to_move) }
+	}
+	
+	fn drop_n(&mut self, n: usize) -> Result<(), usize> {
+		let (head, len) = self.readable();
+		let to_drop = min(len, n);
+		self.consume(head, to_drop);
+		
+		if to_drop == n { Ok(()) }
+			else { Err(to_drop) }
+	}
+}
+impl WriteableSliceQueue<u8> for ShmQueue {
+	fn remaining(&self) -> usize {
+		self.writeable().1
+	}
+	
+	/// The region cannot grow; this call only checks if there is enough space for `n` bytes
+	fn reserve_n(&mut self, n: usize) -> Result<(), usize> {
+		let remaining = self.remaining();
+		if n <= remaining { Ok(()) }
+			else { Err(remaining) }
+	}
+	fn reserved(&self) -> usize {
+		self.remaining()
+	}
+	
+	fn push(&mut self, element: u8) -> Result<(), u8> {
+		self.push_from(&[element]).map_err(|_| element)
+	}
+	fn push_n(&mut self, mut n: Vec<u8>) -> Result<(), Vec<u8>> {
+		match self.push_from(&n) {
+			Ok(_) => Ok(()),
+			Err(pushed) => Err(n.split_off(pushed))
+		}
+	}
+	fn push_from(&mut self, src: &[u8]) -> Result<(), usize> {
+		let (tail, remaining) = self.writeable();
+		let to_push = min(remaining, src.len());
+		self.copy_in(tail, &src[..to_push]);
+		self.commit(tail, to_push);
+		
+		if to_push == src.len() { Ok(()) }
+			else { Err(to_push) }
+	}
+	/// Calls `push_fn` to push up to `n` bytes
+	///
+	/// _Info: Because the shared region cannot be borrowed safely, `push_fn` writes into a
+	/// temporary buffer that is copied into the region afterwards._
+	///
+	/// __Warning: This function panics if `n` exceeds the remaining space or if `push_fn` claims
+	/// that it pushed more than `n` bytes__
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to reserve
+	///  - `push_fn`: The pushing callback
+	///
+	/// Returns either _the amount of bytes pushed_ or _the error `push_fn` returned_
+	fn push_in_place<E>(&mut self, n: usize, mut push_fn: impl FnMut(&mut[u8]) -> Result<usize, E>) -> Result<usize, E> {
+		let (tail, remaining) = self.writeable();
+		assert!(n <= remaining, "`n` exceeds the remaining space");
+		
+		// Call `push_fn` and copy the pushed bytes into the region
+		let mut buffer = vec![0; n];
+		let pushed = push_fn(&mut buffer)?;
+		assert!(pushed <= n, "`push_fn` must not claim that it pushed more elements than `n`");
+		self.copy_in(tail, &buffer[..pushed]);
+		
+		self.commit(tail, pushed);
+		Ok(pushed)
+	}
+}
+impl Read for ShmQueue {
+	/// Pull some bytes from this source into the specified buffer, returning how many bytes were
+	/// read.
+	///
+	/// _Info: `Ok(0)` indicates that the ring is currently empty._
+	///
+	/// __This call never fails; the result is only used for trait-compatibility__
+	fn read(&mut self, buf: &mut[u8]) -> IoResult<usize> {
+		Ok(self.pop_into(buf).map(|_| buf.len()).unwrap_or_else(|moved| moved))
+	}
+}
+impl Write for ShmQueue {
+	/// Write a buffer into this object, returning how many bytes were written.
+	///
+	/// _Info: `Ok(0)` indicates that the ring is currently full._
+	///
+	/// __This call never fails; the result is only used for trait-compatibility__
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		Ok(self.push_from(buf).map(|_| buf.len()).unwrap_or_else(|pushed| pushed))
+	}
+	/// __This call does nothing (and thus never fails); it is only provided for
+	/// trait-compatibility__
+	fn flush(&mut self) -> IoResult<()> {
+		Ok(())
+	}
+}
+impl Drop for ShmQueue {
+	fn drop(&mut self) {
+		unsafe{ sys::munmap(self.mapping as *mut c_void, self.mapping_len) };
+	}
+}
+unsafe impl Send for ShmQueue {}
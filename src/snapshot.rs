@@ -0,0 +1,201 @@
+use super::{
+	SliceQueue, AutoShrinkMode, ReadableSliceQueue, WriteableSliceQueue, ReadableByteQueue, WriteableByteQueue,
+	crc::Crc32
+};
+use std::{ cmp::min, convert::TryFrom, io::{ Read, Write, Error as IoError, ErrorKind as IoErrorKind, Result as IoResult } };
+#[cfg(feature = "unsafe_fast_code")]
+use super::{ Pod, mem::{ reinterpret_vec, copy_vec } };
+#[cfg(feature = "unsafe_fast_code")]
+use std::{ mem, slice };
+
+
+/// The magic bytes at the beginning of a snapshot
+const MAGIC: &[u8; 4] = b"SLQS";
+/// The current snapshot format version
+const VERSION: u8 = 1;
+/// The length of the header (including the header checksum)
+const HEADER_LEN: usize = 4 + 1 + 1 + 4 + 8 + 8 + 4;
+/// The size of the chunks the payload is read in
+const CHUNK_LEN: usize = 64 * 1024;
+
+
+/// Maps the errors of the header queue (which is unlimited and contains the complete header)
+fn header_error<E, T>(_: E) -> T {
+	unreachable!("The header queue is unlimited and complete")
+}
+/// Creates an `InvalidData`-error with `message`
+fn invalid_data(message: &'static str) -> IoError {
+	IoError::new(IoErrorKind::InvalidData, message)
+}
+
+
+/// Writes a snapshot with the given header fields and `payload` into `writer`
+///
+/// Returns either __`Ok(())`__ or the I/O error (`InvalidInput` if `element_size` does not fit into
+/// 32 bits)
+///
+/// The snapshot format (all integers are little endian):
+///  - `magic`: `b"SLQS"`
+///  - `version: u8`: The format version (currently `1`)
+///  - `shrink_mode: u8`: The auto-shrink mode (`0`: opportunistic, `1`: aggressive, `2`: disabled)
+///  - `element_size: u32`: The size of an element in bytes
+///  - `len: u64`: The amount of elements
+///  - `limit: u64`: The limit (`u64::MAX` if the limit is `usize::MAX`)
+///  - `header_checksum: u32`: The CRC-32 of the previous header fields
+///  - `payload`: The elements' bytes
+///  - `payload_checksum: u32`: The CRC-32 of the payload
+fn save<W: Write>(writer: &mut W, element_size: usize, len: usize, limit: usize, mode: AutoShrinkMode, payload: &[u8]) -> IoResult<()> {
+	let mode = match mode {
+		AutoShrinkMode::Opportunistic => 0,
+		AutoShrinkMode::Aggressive => 1,
+		AutoShrinkMode::Disabled => 2
+	};
+	let limit = match limit {
+		usize::MAX => u64::MAX,
+		limit => limit as u64
+	};
+	let element_size = u32::try_from(element_size)
+		.map_err(|_| IoError::new(IoErrorKind::InvalidInput, "The element size does not fit into 32 bits"))?;
+	
+	// Serialize the header
+	let mut header = SliceQueue::with_capacity(HEADER_LEN);
+	header.push_from(MAGIC).unwrap_or_else(header_error);
+	header.push_u8(VERSION).unwrap_or_else(header_error);
+	header.push_u8(mode).unwrap_or_else(header_error);
+	header.push_u32_le(element_size).unwrap_or_else(header_error);
+	header.push_u64_le(len as u64).unwrap_or_else(header_error);
+	header.push_u64_le(limit).unwrap_or_else(header_error);
+	let header_checksum = Crc32::checksum(&header[..]);
+	header.push_u32_le(header_checksum).unwrap_or_else(header_error);
+	
+	// Write the snapshot
+	writer.write_all(&header[..])?;
+	writer.write_all(payload)?;
+	writer.write_all(&Crc32::checksum(payload).to_le_bytes())
+}
+/// Reads and validates a snapshot from `reader`
+///
+/// Returns either __`Ok((payload, limit, mode))`__ or the I/O error (`InvalidData` if the snapshot
+/// is invalid or corrupted)
+fn load<R: Read>(reader: &mut R, element_size: usize) -> IoResult<(Vec<u8>, usize, AutoShrinkMode)> {
+	// Read and validate the header
+	let mut header = [0; HEADER_LEN];
+	reader.read_exact(&mut header)?;
+	let mut header = SliceQueue::from(header.as_ref());
+	let header_checksum = header.peek_u32_le_at(HEADER_LEN - 4).unwrap_or_else(header_error);
+	if Crc32::checksum(&header[..HEADER_LEN - 4]) != header_checksum { return Err(invalid_data("Invalid header checksum")) }
+	if header.pop_n(4).unwrap_or_else(header_error) != MAGIC { return Err(invalid_data("Invalid magic bytes")) }
+	
+	// Parse the header fields
+	let (version, mode) = (header.pop_u8().unwrap_or_else(header_error), header.pop_u8().unwrap_or_else(header_error));
+	let saved_element_size = header.pop_u32_le().unwrap_or_else(header_error);
+	let (len, limit) = (header.pop_u64_le().unwrap_or_else(header_error), header.pop_u64_le().unwrap_or_else(header_error));
+	
+	// Validate the header fields
+	if version != VERSION { return Err(invalid_data("Unsupported snapshot version")) }
+	let mode = match mode {
+		0 => AutoShrinkMode::Opportunistic,
+		1 => AutoShrinkMode::Aggressive,
+		2 => AutoShrinkMode::Disabled,
+		_ => return Err(invalid_data("Invalid auto-shrink mode"))
+	};
+	if saved_element_size as usize != element_size { return Err(invalid_data("The element size does not match")) }
+	let limit = match limit {
+		0 => return Err(invalid_data("The limit is zero")),
+		u64::MAX => usize::MAX,
+		limit => min(limit, usize::MAX as u64) as usize
+	};
+	if len > limit as u64 { return Err(invalid_data("The length exceeds the limit")) }
+	let payload_len = len.checked_mul(element_size as u64).filter(|len| *len <= usize::MAX as u64)
+		.ok_or_else(|| invalid_data("The payload is too large"))? as usize;
+	
+	// Read the payload in chunks so that the allocation only grows with the data that is available
+	let mut payload = Vec::with_capacity(min(payload_len, CHUNK_LEN));
+	while payload.len() < payload_len {
+		let (old_len, chunk_len) = (payload.len(), min(payload_len - payload.len(), CHUNK_LEN));
+		payload.resize(old_len + chunk_len, 0);
+		reader.read_exact(&mut payload[old_len..])?;
+	}
+	
+	// Validate the payload checksum
+	let mut payload_checksum = [0; 4];
+	reader.read_exact(&mut payload_checksum)?;
+	if Crc32::checksum(&payload) != u32::from_le_bytes(payload_checksum) { return Err(invalid_data("Invalid payload checksum")) }
+	Ok((payload, limit, mode))
+}
+
+
+#[cfg(feature = "unsafe_fast_code")]
+impl<T: Pod> SliceQueue<T> {
+	/// Writes a snapshot of `self` (including the limit and the auto-shrink mode) into `writer`
+	///
+	/// _Info: The elements are stored in the native byte order._
+	///
+	/// _Info: Only the elements, the limit and the auto-shrink mode are saved; `consumed_total`, the
+	/// history (and its limit) and the zeroize mode are not, so a restored queue starts at offset
+	/// `0` with the history and the zeroize mode disabled._
+	///
+	/// Parameters:
+	///  - `writer`: The writer to write the snapshot into
+	///
+	/// Returns either __`Ok(())`__ or the I/O error (`InvalidInput` if `T` is larger than
+	/// `u32::MAX` bytes)
+	pub fn save_to<W: Write>(&self, mut writer: W) -> IoResult<()> {
+		let elements = &self[..];
+		let payload = unsafe{ slice::from_raw_parts(elements.as_ptr() as *const u8, mem::size_of_val(elements)) };
+		save(&mut writer, mem::size_of::<T>(), self.len(), self.limit(), self.auto_shrink_mode(), payload)
+	}
+	/// Reads a snapshot from `reader` and restores the queue
+	///
+	/// _Info: The header is validated before the payload is read; the payload is read in chunks, so
+	/// a corrupted snapshot cannot cause an unbounded allocation._
+	///
+	/// Parameters:
+	///  - `reader`: The reader to read the snapshot from
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error (`InvalidData` if the snapshot is invalid,
+	/// corrupted or has a different element size)
+	pub fn load_from<R: Read>(mut reader: R) -> IoResult<Self> {
+		let (payload, limit, mode) = load(&mut reader, mem::size_of::<T>())?;
+		let elements = unsafe{ reinterpret_vec(payload) }.unwrap_or_else(|payload| unsafe{ copy_vec(&payload) });
+		
+		let mut queue = SliceQueue::from(elements);
+		queue.set_limit(limit);
+		queue.set_auto_shrink_mode(mode);
+		Ok(queue)
+	}
+}
+#[cfg(not(feature = "unsafe_fast_code"))]
+impl SliceQueue<u8> {
+	/// Writes a snapshot of `self` (including the limit and the auto-shrink mode) into `writer`
+	///
+	/// _Info: Only the elements, the limit and the auto-shrink mode are saved; `consumed_total` and
+	/// the history (and its limit) are not, so a restored queue starts at offset `0` with the
+	/// history disabled._
+	///
+	/// Parameters:
+	///  - `writer`: The writer to write the snapshot into
+	///
+	/// Returns either __`Ok(())`__ or the I/O error that occurred
+	pub fn save_to<W: Write>(&self, mut writer: W) -> IoResult<()> {
+		save(&mut writer, 1, self.len(), self.limit(), self.auto_shrink_mode(), &self[..])
+	}
+	/// Reads a snapshot from `reader` and restores the queue
+	///
+	/// _Info: The header is validated before the payload is read; the payload is read in chunks, so
+	/// a corrupted snapshot cannot cause an unbounded allocation._
+	///
+	/// Parameters:
+	///  - `reader`: The reader to read the snapshot from
+	///
+	/// Returns either __`Ok(queue)`__ or the I/O error (`InvalidData` if the snapshot is invalid,
+	/// corrupted or has a different element size)
+	pub fn load_from<R: Read>(mut reader: R) -> IoResult<Self> {
+		let (payload, limit, mode) = load(&mut reader, 1)?;
+		
+		let mut queue = SliceQueue::from(payload);
+		queue.set_limit(limit);
+		queue.set_auto_shrink_mode(mode);
+		Ok(queue)
+	}
+}
@@ -0,0 +1,373 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{
+	cmp::min, env, process,
+	fs::{ self, File, OpenOptions }, path::{ Path, PathBuf },
+	sync::atomic::{ AtomicUsize, Ordering },
+	io::{ self, Read, Write, Seek, SeekFrom, Result as IoResult }
+};
+
+
+/// A counter to create unique file names within this process
+static SPILL_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+
+/// A byte queue that spills the data beyond a memory threshold into a temporary file
+///
+/// The queue consists of two tiers: the memory tier at the front and the file tier at the end.
+/// Bytes are appended to the memory tier until the threshold is reached; after that they are
+/// appended to the file tier until it has been read back completely. The `ReadableSliceQueue`
+/// view is the memory tier, which is refilled from the file tier after every consuming call, so
+/// the bytes are read back transparently and in order. The remaining space and the limit cover
+/// both tiers. The temporary file is created on demand and deleted when the queue is dropped.
+///
+/// _Info: If spilling or reading back fails, the I/O error is stored and reported by the next
+/// `flush`-call (pushes that fail return `Err` as if the limit had been reached)._
+#[derive(Debug)]
+pub struct SpillQueue {
+	memory: SliceQueue<u8>,
+	file: Option<(File, PathBuf)>,
+	file_start: u64,
+	file_end: u64,
+	spill_dir: PathBuf,
+	threshold: usize,
+	limit: usize,
+	error: Option<io::Error>
+}
+impl SpillQueue {
+	/// Creates a new `SpillQueue`
+	///
+	/// __Warning: Panics if `threshold` is `0`__
+	///
+	/// Parameters:
+	///  - `threshold`: The maximum amount of bytes to keep in memory
+	///
+	/// Returns __the new `SpillQueue`__
+	pub fn new(threshold: usize) -> Self {
+		Self::with_limit(threshold, usize::MAX)
+	}
+	/// Creates a new `SpillQueue` with a predefined limit (the default limit is `usize::MAX`)
+	///
+	/// __Warning: Panics if `threshold` or `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `threshold`: The maximum amount of bytes to keep in memory
+	///  - `limit`: The maximum amount of bytes that can be stored by `self` (in both tiers)
+	///
+	/// Returns __the new `SpillQueue`__
+	pub fn with_limit(threshold: usize, limit: usize) -> Self {
+		assert!(threshold > 0, "`threshold` is `0`");
+		assert!(limit > 0, "`limit` is `0`");
+		SpillQueue {
+			memory: SliceQueue::new(), file: None, file_start: 0, file_end: 0,
+			spill_dir: env::temp_dir(), threshold, limit, error: None
+		}
+	}
+	
+	
+	/// Sets the directory to create the temporary file in (the default directory is
+	/// `env::temp_dir()`)
+	///
+	/// _Info: This only affects temporary files that are created after this call._
+	///
+	/// Parameters:
+	///  - `spill_dir`: The directory to create the temporary file in
+	pub fn set_spill_dir(&mut self, spill_dir: impl Into<PathBuf>) {
+		self.spill_dir = spill_dir.into()
+	}
+	/// The path of the temporary file
+	///
+	/// Returns either __`Some(path)`__ if the temporary file exists or __`None`__ otherwise
+	pub fn spill_path(&self) -> Option<&Path> {
+		self.file.as_ref().map(|(_, path)| path.as_path())
+	}
+	/// The memory threshold
+	///
+	/// Returns __the maximum amount of bytes that are kept in memory__
+	pub fn threshold(&self) -> usize {
+		self.threshold
+	}
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of bytes that can be stored by `self` (in both tiers)
+	pub fn set_limit(&mut self, limit: usize) {
+		assert!(limit > 0, "`limit` is `0`");
+		self.limit = limit
+	}
+	
+	
+	/// The amount of bytes stored in both tiers
+	///
+	/// Returns __the amount of bytes stored in `self`__
+	pub fn total_len(&self) -> usize {
+		self.memory.len() + self.spilled_len()
+	}
+	/// The amount of bytes stored in the file tier
+	///
+	/// Returns __the amount of bytes stored in the temporary file__
+	pub fn spilled_len(&self) -> usize {
+		(self.file_end - self.file_start) as usize
+	}
+	
+	
+	/// Appends the bytes in `src` at the end (up to the limit)
+	///
+	/// Returns __the amount of bytes appended__ and either __`Ok(())`__ or the I/O error that
+	/// occurred while spilling the bytes (the bytes that fit into the memory tier are appended
+	/// nonetheless)
+	fn append(&mut self, src: &[u8]) -> (usize, IoResult<()>) {
+		let src = &src[..min(src.len(), self.remaining())];
+		
+		// Fill the memory tier as long as nothing has been spilled
+		let in_memory = match self.spilled_len() {
+			0 => min(src.len(), self.threshold.saturating_sub(self.memory.len())),
+			_ => 0
+		};
+		self.memory.push_from(&src[..in_memory]).unwrap_or_else(|_| unreachable!("The memory tier is unlimited"));
+		
+		// Spill the remaining bytes
+		if in_memory < src.len() {
+			if let Err(e) = self.spill(&src[in_memory..]) { return (in_memory, Err(e)) }
+		}
+		(src.len(), Ok(()))
+	}
+	/// Appends `bytes` to the file tier
+	fn spill(&mut self, bytes: &[u8]) -> IoResult<()> {
+		let file_end = self.file_end;
+		let file = self.spill_file()?;
+		file.seek(SeekFrom::Start(file_end))?;
+		file.write_all(bytes)?;
+		self.file_end += bytes.len() as u64;
+		Ok(())
+	}
+	/// Returns the temporary file and creates it if necessary
+	fn spill_file(&mut self) -> IoResult<&mut File> {
+		if self.file.is_none() {
+			loop {
+				// Create a new file with a unique name
+				let name = format!("slice_queue-{}-{}.spill", process::id(), SPILL_FILE_COUNTER.fetch_add(1, Ordering::Relaxed));
+				let path = self.spill_dir.join(name);
+				match OpenOptions::new().read(true).write(true).create_new(true).open(&path) {
+					Ok(file) => break self.file = Some((file, path)),
+					Err(ref e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+					Err(e) => return Err(e)
+				}
+			}
+		}
+		Ok(self.file.as_mut().map(|(file, _)| file).unwrap_or_else(|| unreachable!("The file has been created")))
+	}
+	/// Reads spilled bytes back into memory until the memory tier reaches the threshold (or the
+	/// file tier is empty)
+	fn unspill(&mut self) -> IoResult<()> {
+		let to_read = min(self.threshold.saturating_sub(self.memory.len()), self.spilled_len());
+		if to_read > 0 {
+			let (file, _) = self.file.as_mut().unwrap_or_else(|| unreachable!("There are spilled bytes"));
+			file.seek(SeekFrom::Start(self.file_start))?;
+			self.memory.push_in_place(to_read, |buf| file.read_exact(buf).map(|_| to_read))?;
+			self.file_start += to_read as u64;
+		}
+		
+		self.truncate_file();
+		Ok(())
+	}
+	/// Refills the memory tier after a consuming call and stores the I/O error if this fails
+	fn refill(&mut self) {
+		if let Err(e) = self.unspill() { self.error = Some(e) }
+	}
+	/// Truncates the temporary file if it has been read back completely
+	///
+	/// _Info: If the file cannot be truncated, the bytes are appended after the old bytes instead._
+	fn truncate_file(&mut self) {
+		if let Some((file, _)) = self.file.as_mut() {
+			if self.file_start == self.file_end && self.file_end > 0 && file.set_len(0).is_ok() {
+				self.file_start = 0;
+				self.file_end = 0;
+			}
+		}
+	}
+}
+impl ReadableSliceQueue<u8> for SpillQueue {
+	/// The amount of bytes stored in the memory tier
+	///
+	/// _Info: Use `total_len` to get the amount of bytes stored in both tiers._
+	///
+	/// Returns __the amount of bytes that can be peeked at or consumed right now__
+	fn len(&self) -> usize {
+		self.memory.len()
+	}
+	/// Checks if there are __no__ bytes stored in both tiers
+	///
+	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
+	fn is_empty(&self) -> bool {
+		self.total_len() == 0
+	}
+	fn limit(&self) -> usize {
+		self.limit
+	}
+	
+	fn peek(&self) -> Option<&u8> {
+		self.memory.peek()
+	}
+	fn peek_n(&self, n: usize) -> Result<&[u8], &[u8]> {
+		self.memory.peek_n(n)
+	}
+	
+	fn pop(&mut self) -> Result<u8, ()> {
+		let result = self.memory.pop();
+		self.refill();
+		result
+	}
+	fn pop_n(&mut self, n: usize) -> Result<Vec<u8>, Vec<u8>> {
+		let result = self.memory.pop_n(n);
+		self.refill();
+		result
+	}
+	fn pop_into(&mut self, dst: &mut[u8]) -> Result<(), usize> {
+		let result = self.memory.pop_into(dst);
+		self.refill();
+		result
+	}
+	
+	fn drop_n(&mut self, n: usize) -> Result<(), usize> {
+		let result = self.memory.drop_n(n);
+		self.refill();
+		result
+	}
+}
+impl WriteableSliceQueue<u8> for SpillQueue {
+	/// The amount of space remaining until `self.limit` is reached (in both tiers)
+	///
+	/// Returns __the amount of space remaining in `self` until `self.limit` is reached__
+	fn remaining(&self) -> usize {
+		self.limit.saturating_sub(self.total_len())
+	}
+	
+	/// Reserves an additional amount of memory to append `n` bytes without reallocating
+	///
+	/// _Info: Only the free space of the memory tier below the threshold is reserved; bytes that
+	/// are spilled do not need a reservation._
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes that we should be able to append without reallocating
+	///
+	/// Returns either _nothing_ if the space for `n` bytes could be reserved or _the amount of
+	/// bytes reserved_ if `n` was greater than `self.remaining`.
+	fn reserve_n(&mut self, n: usize) -> Result<(), usize> {
+		let to_reserve = min(self.remaining(), n);
+		let in_memory = match self.spilled_len() {
+			0 => min(to_reserve, self.threshold.saturating_sub(self.memory.len())),
+			_ => 0
+		};
+		self.memory.reserve_n(in_memory).unwrap_or_else(|_| unreachable!("The memory tier is unlimited"));
+		
+		if to_reserve == n { Ok(()) }
+			else { Err(to_reserve) }
+	}
+	fn reserved(&self) -> usize {
+		min(self.memory.reserved(), self.remaining())
+	}
+	
+	fn push(&mut self, element: u8) -> Result<(), u8> {
+		self.push_from(&[element]).map_err(|_| element)
+	}
+	fn push_n(&mut self, mut n: Vec<u8>) -> Result<(), Vec<u8>> {
+		match self.push_from(&n) {
+			Ok(()) => Ok(()),
+			Err(appended) => Err(n.split_off(appended))
+		}
+	}
+	/// Appends the bytes in `src` at the end
+	///
+	/// Parameters:
+	///  - `src`: A slice containing the bytes to append
+	///
+	/// Returns either __`Ok(())`__ if `src` was appended completely or __`Err(appended_count)`__
+	/// if `src` was only appended partially because `self.limit` would have been exceeded or
+	/// because spilling failed (the I/O error is reported by the next `flush`-call)
+	fn push_from(&mut self, src: &[u8]) -> Result<(), usize> {
+		match self.append(src) {
+			(appended, Ok(())) if appended == src.len() => Ok(()),
+			(appended, Ok(())) => Err(appended),
+			(appended, Err(e)) => {
+				self.error = Some(e);
+				Err(appended)
+			}
+		}
+	}
+	/// Calls `push_fn` to push up to `n` bytes in place
+	///
+	/// _Info: If the bytes do not fit into the memory tier, they are written to a temporary buffer
+	/// and spilled afterwards; if spilling fails, only the bytes that fit into memory are kept and
+	/// the I/O error is reported by the next `flush`-call._
+	///
+	/// __Warning: This function panics if `self.limit` is exceeded__
+	///
+	/// Parameters:
+	///  - `n`: The amount of bytes to reserve
+	///  - `push_fn`: The pushing callback
+	///
+	/// Returns either _the amount of bytes pushed_ or _the error `push_fn` returned_
+	fn push_in_place<E>(&mut self, n: usize, mut push_fn: impl FnMut(&mut[u8]) -> Result<usize, E>) -> Result<usize, E> {
+		assert!(self.remaining() >= n, "`self.len() + n` is larger than `self.limit`");
+		
+		// Push directly into the memory tier if the bytes fit
+		if self.spilled_len() == 0 && self.memory.len() + n <= self.threshold {
+			return self.memory.push_in_place(n, push_fn)
+		}
+		
+		let mut buf = vec![0; n];
+		let pushed = push_fn(&mut buf)?;
+		assert!(pushed <= n, "`push_fn` must not claim that it pushed more elements than `n`");
+		Ok(self.push_from(&buf[..pushed]).map(|_| pushed).unwrap_or_else(|appended| appended))
+	}
+}
+impl Read for SpillQueue {
+	/// Pull some bytes from this source into the specified buffer, returning how many bytes were
+	/// read.
+	///
+	/// __This call only fails if the spilled bytes cannot be read back__
+	fn read(&mut self, buf: &mut[u8]) -> IoResult<usize> {
+		// Make sure that the memory tier is filled if possible
+		self.unspill()?;
+		let moved = self.memory.pop_into(buf).map(|_| buf.len()).unwrap_or_else(|moved| moved);
+		self.refill();
+		Ok(moved)
+	}
+}
+impl Write for SpillQueue {
+	/// Write a buffer into this object, returning how many bytes were written.
+	///
+	/// __This call only fails if the bytes cannot be spilled__
+	fn write(&mut self, buf: &[u8]) -> IoResult<usize> {
+		match self.append(buf) {
+			(0, Err(e)) => Err(e),
+			(appended, Err(e)) => {
+				// Report the bytes in memory so that they are not appended again by a retry
+				self.error = Some(e);
+				Ok(appended)
+			},
+			(appended, Ok(())) => Ok(appended)
+		}
+	}
+	/// Flushes the temporary file and reports the I/O error that occurred during a
+	/// `WriteableSliceQueue`- or `ReadableSliceQueue`-call since the last `flush`-call (if any)
+	fn flush(&mut self) -> IoResult<()> {
+		if let Some((file, _)) = self.file.as_mut() { file.flush()?; }
+		match self.error.take() {
+			Some(e) => Err(e),
+			None => Ok(())
+		}
+	}
+}
+impl Drop for SpillQueue {
+	fn drop(&mut self) {
+		if let Some((file, path)) = self.file.take() {
+			drop(file);
+			let _ = fs::remove_file(path);
+		}
+	}
+}
@@ -0,0 +1,92 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{ cmp::min, fmt, str::{ self, Utf8Error } };
+
+
+/// Validates `bytes` and returns the longest valid UTF-8 prefix
+///
+/// Returns either __`Ok(prefix)`__ or __`Err(Utf8Error)`__ if `bytes` starts with an invalid
+/// sequence (an incomplete trailing sequence is not an error and results in an empty prefix)
+fn valid_prefix(bytes: &[u8]) -> Result<&str, Utf8Error> {
+	match str::from_utf8(bytes) {
+		Ok(string) => Ok(string),
+		Err(e) if e.valid_up_to() > 0 || e.error_len().is_none() => {
+			Ok(str::from_utf8(&bytes[..e.valid_up_to()]).unwrap_or_else(|_| unreachable!("The prefix is valid")))
+		},
+		Err(e) => Err(e)
+	}
+}
+
+
+impl SliceQueue<u8> {
+	/// Take a look at the longest valid UTF-8 prefix __without__ consuming it
+	///
+	/// _Info: The prefix ends before the first invalid or incomplete sequence; use `pop_str` or
+	/// `pop_char` to detect invalid sequences._
+	///
+	/// Returns __the longest valid UTF-8 prefix__
+	pub fn peek_str(&self) -> &str {
+		valid_prefix(&self[..]).unwrap_or("")
+	}
+	/// Consumes up to `n_bytes` bytes that form valid UTF-8 and returns them
+	///
+	/// _Info: A code point is never split; if the `n_bytes`-th byte is within a code point, only the
+	/// bytes before that code point are consumed. Consuming stops before the first invalid
+	/// sequence._
+	///
+	/// Parameters:
+	///  - `n_bytes`: The maximum amount of bytes to consume
+	///
+	/// Returns either __`Ok(string)`__ (which is empty if not enough bytes are available to form a
+	/// complete code point) or __`Err(Utf8Error)`__ if the first bytes are an invalid sequence
+	/// (nothing is consumed in this case; `Utf8Error::error_len` tells you how many bytes to drop to
+	/// skip it)
+	pub fn pop_str(&mut self, n_bytes: usize) -> Result<String, Utf8Error> {
+		let string = valid_prefix(&self[..min(n_bytes, self.len())])?.to_string();
+		self.drop_n(string.len()).unwrap_or_else(|_| unreachable!("The string is buffered completely"));
+		Ok(string)
+	}
+	/// Consumes the first code point and returns it
+	///
+	/// Returns either __`Ok(Some(char))`__, __`Ok(None)`__ if not enough bytes are available to form
+	/// a complete code point or __`Err(Utf8Error)`__ if the first bytes are an invalid sequence
+	/// (nothing is consumed in this case; `Utf8Error::error_len` tells you how many bytes to drop to
+	/// skip it)
+	pub fn pop_char(&mut self) -> Result<Option<char>, Utf8Error> {
+		let char = match valid_prefix(&self[..min(4, self.len())])?.chars().next() {
+			Some(char) => char,
+			None => return Ok(None)
+		};
+		self.drop_n(char.len_utf8()).unwrap_or_else(|_| unreachable!("The char is buffered completely"));
+		Ok(Some(char))
+	}
+	
+	/// Formats `args` and appends the result at the end
+	///
+	/// _Info: Unlike `write!`, this call is all-or-nothing because the formatted string is
+	/// buffered before it is appended. It also avoids the ambiguity of `write!` if both
+	/// `std::io::Write` and `std::fmt::Write` are in scope (e.g.
+	/// `queue.push_fmt(format_args!("{}", 7))`)._
+	///
+	/// Parameters:
+	///  - `args`: The arguments to format
+	///
+	/// Returns either __`Ok(())`__ if the formatted string was appended or __`Err(fmt::Error)`__ if
+	/// it was not appended because the limit would have been exceeded
+	pub fn push_fmt(&mut self, args: fmt::Arguments) -> fmt::Result {
+		fmt::Write::write_str(self, &fmt::format(args))
+	}
+}
+impl fmt::Write for SliceQueue<u8> {
+	/// Appends the UTF-8 bytes of `s` at the end
+	///
+	/// _Info: `write!` calls `write_str` once per fragment, so a failing `write!` may have appended
+	/// the preceding fragments; use `push_fmt` for an all-or-nothing append. `write!` is ambiguous if
+	/// `std::io::Write` is also in scope; use `push_fmt` or `fmt::Write::write_fmt` in this case._
+	///
+	/// __This call fails if `s` cannot be appended completely because the limit would have been
+	/// exceeded (nothing of `s` is appended in this case)__
+	fn write_str(&mut self, s: &str) -> fmt::Result {
+		if self.remaining() < s.len() { return Err(fmt::Error) }
+		self.push_from(s.as_bytes()).map_err(|_| unreachable!("The string fits into the queue"))
+	}
+}
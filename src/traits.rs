@@ -7,6 +7,16 @@ pub trait ReadableSliceQueue<T> {
 	///
 	/// Returns either __`true`__ if `self` is empty or __`false`__ otherwise
 	fn is_empty(&self) -> bool;
+	/// The maximum amount of elements that can be stored
+	///
+	/// _Info: The default implementation returns `usize::MAX`; implementors that enforce a limit
+	/// should override this method so that readers (e.g. decoders) can reject data that will
+	/// never fit._
+	///
+	/// Returns __the current size-limit of `self`__
+	fn limit(&self) -> usize {
+		usize::MAX
+	}
 	
 	/// Take a look at the first element __without__ consuming it
 	///
@@ -0,0 +1,224 @@
+use super::{ SliceQueue, ReadableSliceQueue, WriteableSliceQueue };
+use std::{ collections::VecDeque, time::{ Duration, Instant } };
+
+
+/// A source of timestamps (implement this trait to fake the time in tests)
+pub trait Clock {
+	/// The current time
+	///
+	/// Returns __the current time__
+	fn now(&self) -> Instant;
+}
+/// The system's monotonic clock
+#[derive(Copy, Clone, Debug, Default)]
+pub struct SystemClock;
+impl Clock for SystemClock {
+	fn now(&self) -> Instant {
+		Instant::now()
+	}
+}
+
+
+/// A queue that discards elements after a time-to-live
+///
+/// Every `push*`-call stores one timestamp for the pushed batch. The timestamps are addressed by
+/// the absolute offset of the batch end, so they stay in sync with the elements regardless of how
+/// the elements are consumed. `expire` discards the batches that are older than the TTL.
+#[derive(Clone, Debug)]
+pub struct ExpiringSliceQueue<T, C = SystemClock> {
+	queue: SliceQueue<T>,
+	batches: VecDeque<(u64, Instant)>,
+	ttl: Duration,
+	clock: C
+}
+impl<T> ExpiringSliceQueue<T> {
+	/// Creates a new `ExpiringSliceQueue` that uses the system clock
+	///
+	/// Parameters:
+	///  - `ttl`: The time after which the elements expire
+	///
+	/// Returns __the new `ExpiringSliceQueue`__
+	pub fn new(ttl: Duration) -> Self {
+		Self::with_clock(ttl, SystemClock)
+	}
+}
+impl<T, C: Clock> ExpiringSliceQueue<T, C> {
+	/// Creates a new `ExpiringSliceQueue` that uses `clock`
+	///
+	/// Parameters:
+	///  - `ttl`: The time after which the elements expire
+	///  - `clock`: The clock to timestamp the pushed batches with
+	///
+	/// Returns __the new `ExpiringSliceQueue`__
+	pub fn with_clock(ttl: Duration, clock: C) -> Self {
+		ExpiringSliceQueue{ queue: SliceQueue::new(), batches: VecDeque::new(), ttl, clock }
+	}
+	
+	
+	/// Sets a new limit (the default limit is `usize::MAX`)
+	///
+	/// _Info: The limit is only enforced during the `push*`-calls._
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that can be stored by `self`
+	pub fn set_limit(&mut self, limit: usize) {
+		self.queue.set_limit(limit)
+	}
+	/// Sets a new time-to-live
+	///
+	/// _Info: The new TTL also applies to the elements that have already been pushed._
+	///
+	/// Parameters:
+	///  - `ttl`: The time after which the elements expire
+	pub fn set_ttl(&mut self, ttl: Duration) {
+		self.ttl = ttl
+	}
+	/// The current time-to-live
+	///
+	/// Returns __the time after which the elements expire__
+	pub fn ttl(&self) -> Duration {
+		self.ttl
+	}
+	/// The clock
+	///
+	/// Returns __a reference to the clock__
+	pub fn clock(&self) -> &C {
+		&self.clock
+	}
+	
+	
+	/// The timestamp of the oldest element
+	///
+	/// Returns either __`Some(timestamp)`__ or __`None`__ if `self` is empty
+	pub fn oldest(&self) -> Option<Instant> {
+		let consumed = self.queue.consumed_total();
+		self.batches.iter().find(|(end, _)| *end > consumed).map(|(_, timestamp)| *timestamp)
+	}
+	/// The time at which the oldest element expires (e.g. to schedule the next `expire`-call)
+	///
+	/// Returns either __`Some(time)`__ or __`None`__ if `self` is empty or the time is not
+	/// representable
+	pub fn next_expiry(&self) -> Option<Instant> {
+		self.oldest().and_then(|timestamp| timestamp.checked_add(self.ttl))
+	}
+	/// Discards all elements that are older than the TTL at `now`
+	///
+	/// _Info: An element expires if `now` is at or after its timestamp plus the TTL. The elements
+	/// are discarded using `drop_n`._
+	///
+	/// Parameters:
+	///  - `now`: The current time
+	///
+	/// Returns __the amount of discarded elements__
+	pub fn expire(&mut self, now: Instant) -> usize {
+		self.prune();
+		
+		// Find the end of the last expired batch
+		let mut expired_end = self.queue.consumed_total();
+		while let Some((end, timestamp)) = self.batches.front().cloned() {
+			match timestamp.checked_add(self.ttl) {
+				Some(expiry) if expiry <= now => expired_end = end,
+				_ => break
+			}
+			self.batches.pop_front();
+		}
+		
+		let expired = (expired_end - self.queue.consumed_total()) as usize;
+		self.queue.drop_n(expired).unwrap_or_else(|_| unreachable!("The expired elements are available"));
+		expired
+	}
+	/// Discards all elements that are older than the TTL using the current time of the clock
+	///
+	/// Returns __the amount of discarded elements__
+	pub fn expire_now(&mut self) -> usize {
+		let now = self.clock.now();
+		self.expire(now)
+	}
+	
+	
+	/// Stores the timestamp for the elements up to the current end (batches with the same timestamp
+	/// are merged)
+	fn stamp(&mut self) {
+		let (end, now) = (self.queue.pushed_total(), self.clock.now());
+		match self.batches.back_mut() {
+			Some(batch) if batch.0 == end => (),
+			Some(batch) if batch.1 == now => batch.0 = end,
+			_ => self.batches.push_back((end, now))
+		}
+		self.prune();
+	}
+	/// Removes the timestamps of the batches that have been consumed completely
+	fn prune(&mut self) {
+		let consumed = self.queue.consumed_total();
+		while self.batches.front().is_some_and(|(end, _)| *end <= consumed) {
+			self.batches.pop_front();
+		}
+	}
+}
+impl<T, C: Clock> ReadableSliceQueue<T> for ExpiringSliceQueue<T, C> {
+	fn len(&self) -> usize {
+		self.queue.len()
+	}
+	fn is_empty(&self) -> bool {
+		self.queue.is_empty()
+	}
+	fn limit(&self) -> usize {
+		self.queue.limit()
+	}
+	
+	fn peek(&self) -> Option<&T> {
+		self.queue.peek()
+	}
+	fn peek_n(&self, n: usize) -> Result<&[T], &[T]> {
+		self.queue.peek_n(n)
+	}
+	
+	fn pop(&mut self) -> Result<T, ()> {
+		self.queue.pop()
+	}
+	fn pop_n(&mut self, n: usize) -> Result<Vec<T>, Vec<T>> {
+		self.queue.pop_n(n)
+	}
+	fn pop_into(&mut self, dst: &mut[T]) -> Result<(), usize> {
+		self.queue.pop_into(dst)
+	}
+	
+	fn drop_n(&mut self, n: usize) -> Result<(), usize> {
+		self.queue.drop_n(n)
+	}
+}
+impl<T, C: Clock> WriteableSliceQueue<T> for ExpiringSliceQueue<T, C> {
+	fn remaining(&self) -> usize {
+		self.queue.remaining()
+	}
+	
+	fn reserve_n(&mut self, n: usize) -> Result<(), usize> {
+		self.queue.reserve_n(n)
+	}
+	fn reserved(&self) -> usize {
+		self.queue.reserved()
+	}
+	
+	fn push(&mut self, element: T) -> Result<(), T> {
+		let result = self.queue.push(element);
+		self.stamp();
+		result
+	}
+	fn push_n(&mut self, n: Vec<T>) -> Result<(), Vec<T>> {
+		let result = self.queue.push_n(n);
+		self.stamp();
+		result
+	}
+	fn push_from(&mut self, src: &[T]) -> Result<(), usize> where T: Clone {
+		let result = self.queue.push_from(src);
+		self.stamp();
+		result
+	}
+	fn push_in_place<E>(&mut self, n: usize, push_fn: impl FnMut(&mut[T]) -> Result<usize, E>) -> Result<usize, E> where T: Default {
+		let result = self.queue.push_in_place(n, push_fn);
+		self.stamp();
+		result
+	}
+}
@@ -0,0 +1,76 @@
+use super::SliceQueue;
+use std::ops::{ Deref, DerefMut };
+
+
+/// A `SliceQueue<T>` with the zeroize mode enabled that also wipes its allocations when it is
+/// dropped
+///
+/// `SliceQueue` itself does not implement `Drop` (so it does not restrict the lifetimes of borrowed
+/// elements); use this wrapper if the memory must also be wiped on drop. The queue is accessed
+/// using `Deref` and `DerefMut` (e.g. `&mut *queue` to pass it to a codec).
+///
+/// _Info: Disabling the zeroize mode using `set_zeroize` only stops wiping vacated memory; the
+/// allocations are still wiped on drop._
+#[derive(Clone, Debug)]
+pub struct ZeroizingSliceQueue<T> {
+	queue: SliceQueue<T>
+}
+impl<T> ZeroizingSliceQueue<T> {
+	/// Creates a new `ZeroizingSliceQueue`
+	///
+	/// Returns __the new `ZeroizingSliceQueue`__
+	pub fn new() -> Self {
+		Self::from(SliceQueue::new())
+	}
+	/// Creates a new `ZeroizingSliceQueue` with a preallocated capacity `n`
+	///
+	/// Parameters:
+	///  - `n`: The capacity to preallocate
+	///
+	/// Returns __the new `ZeroizingSliceQueue`__
+	pub fn with_capacity(n: usize) -> Self {
+		Self::from(SliceQueue::with_capacity(n))
+	}
+	/// Creates a new `ZeroizingSliceQueue` with a predefined `limit` (the default limit is
+	/// `usize::MAX`)
+	///
+	/// __Warning: Panics if `limit` is `0`__
+	///
+	/// Parameters:
+	///  - `limit`: The maximum amount of elements that can be stored by `self`
+	///
+	/// Returns __the new `ZeroizingSliceQueue`__
+	pub fn with_limit(limit: usize) -> Self {
+		Self::from(SliceQueue::with_limit(limit))
+	}
+}
+impl<T> From<SliceQueue<T>> for ZeroizingSliceQueue<T> {
+	/// Wraps `queue` and enables its zeroize mode
+	///
+	/// _Info: Memory that `queue` has freed before is not wiped._
+	fn from(mut queue: SliceQueue<T>) -> Self {
+		queue.set_zeroize(true);
+		ZeroizingSliceQueue{ queue }
+	}
+}
+impl<T> Deref for ZeroizingSliceQueue<T> {
+	type Target = SliceQueue<T>;
+	fn deref(&self) -> &SliceQueue<T> {
+		&self.queue
+	}
+}
+impl<T> DerefMut for ZeroizingSliceQueue<T> {
+	fn deref_mut(&mut self) -> &mut SliceQueue<T> {
+		&mut self.queue
+	}
+}
+impl<T> Drop for ZeroizingSliceQueue<T> {
+	fn drop(&mut self) {
+		self.queue.wipe()
+	}
+}
+impl<T> Default for ZeroizingSliceQueue<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
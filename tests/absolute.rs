@@ -0,0 +1,66 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::io::Read;
+
+
+#[test]
+fn test_totals() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	assert_eq!((queue.consumed_total(), queue.pushed_total()), (0, 9));
+	
+	queue.pop().unwrap();
+	queue.pop_n(2).unwrap();
+	queue.pop_into(&mut [0; 2]).unwrap();
+	queue.drop_n(1).unwrap();
+	queue.read_exact(&mut [0; 1]).unwrap();
+	assert_eq!((queue.consumed_total(), queue.pushed_total()), (7, 9));
+	
+	queue.push_from(b"!?").unwrap();
+	assert_eq!((queue.consumed_total(), queue.pushed_total()), (7, 11));
+	assert_eq!(queue.clone().consumed_total(), 7);
+}
+
+#[test]
+fn test_get_peek_abs() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	queue.drop_n(4).unwrap();
+	
+	assert_eq!(queue.get_abs(4), Some(&b'o'));
+	assert_eq!(queue.get_abs(8), Some(&b'e'));
+	assert_eq!(queue.peek_abs(4..9), Some(b"olope".as_ref()));
+	assert_eq!(queue.peek_abs(6..6), Some(b"".as_ref()));
+}
+
+#[test]
+fn test_drop_until_abs() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	queue.drop_until_abs(4).unwrap();
+	assert_eq!(&queue[..], b"olope");
+	
+	// Offsets that have already been consumed are ignored
+	queue.drop_until_abs(2).unwrap();
+	assert_eq!(queue.consumed_total(), 4);
+}
+
+
+#[test]
+fn test_abs_out_of_range() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	queue.drop_n(4).unwrap();
+	
+	assert_eq!(queue.get_abs(3), None);
+	assert_eq!(queue.get_abs(9), None);
+	assert_eq!(queue.peek_abs(3..6), None);
+	assert_eq!(queue.peek_abs(6..10), None);
+	#[allow(clippy::reversed_empty_ranges)]
+	let reversed = queue.peek_abs(6..5);
+	assert_eq!(reversed, None);
+}
+
+#[test]
+fn test_drop_until_abs_beyond_end() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	queue.drop_n(4).unwrap();
+	assert_eq!(queue.drop_until_abs(12), Err(5));
+	assert_eq!(queue.consumed_total(), 9);
+}
@@ -0,0 +1,88 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::{ thread, time::Duration };
+
+
+#[test]
+fn test_batch() {
+	let queue = SharedSliceQueue::with_limit(8);
+	queue.push_batch(b"Test".to_vec()).unwrap();
+	assert_eq!(queue.push_batch(b"olope".to_vec()), Err(b"olope".to_vec()));
+	assert_eq!(queue.push_batch_from(b"olope"), Err(5));
+	assert_eq!(queue.len(), 4);
+	queue.push_batch_from(b"olop").unwrap();
+	
+	assert_eq!(queue.pop_batch(9), None);
+	assert_eq!(queue.pop_batch(5), Some(b"Testo".to_vec()));
+	assert_eq!(queue.pop_batch(0), Some(Vec::new()));
+	assert_eq!(queue.len(), 3);
+}
+
+#[test]
+fn test_batch_blocking() {
+	let queue = SharedSliceQueue::with_limit(4);
+	let handle = queue.clone();
+	let thread = thread::spawn(move || {
+		handle.push_batch_blocking(b"Test".to_vec(), None).unwrap();
+		handle.push_batch_blocking(b"olope".to_vec(), None).unwrap_err();
+		handle.push_batch_blocking(b"olo".to_vec(), None).unwrap();
+	});
+	
+	assert_eq!(queue.pop_batch_blocking(2, Some(Duration::from_secs(10))), Some(b"Te".to_vec()));
+	assert_eq!(queue.pop_batch_blocking(5, None), None);
+	assert_eq!(queue.pop_batch_blocking(2, None), Some(b"st".to_vec()));
+	assert_eq!(queue.pop_batch_blocking(3, None), Some(b"olo".to_vec()));
+	thread.join().unwrap();
+	
+	// Validate the timeouts
+	assert_eq!(queue.pop_batch_blocking(1, Some(Duration::from_millis(10))), None);
+	queue.push_batch(b"Test".to_vec()).unwrap();
+	assert_eq!(queue.push_batch_blocking(b"!".to_vec(), Some(Duration::from_millis(10))), Err(b"!".to_vec()));
+}
+
+#[test]
+fn test_batch_blocking_limit_lowered() {
+	let queue = SharedSliceQueue::with_limit(4);
+	queue.push_batch(b"Te".to_vec()).unwrap();
+	
+	// Lower the limit while the calls are waiting
+	let (pusher, popper) = (queue.clone(), queue.clone());
+	let push_thread = thread::spawn(move || pusher.push_batch_blocking(b"sto".to_vec(), None));
+	let pop_thread = thread::spawn(move || popper.pop_batch_blocking(3, None));
+	thread::sleep(Duration::from_millis(50));
+	queue.set_limit(2);
+	
+	assert_eq!(push_thread.join().unwrap(), Err(b"sto".to_vec()));
+	assert_eq!(pop_thread.join().unwrap(), None);
+	assert_eq!(queue.len(), 2);
+}
+
+#[test]
+fn test_batch_mpmc() {
+	const PRODUCERS: usize = 4;
+	const BATCHES: usize = 200;
+	let queue = SharedSliceQueue::with_limit(64);
+	
+	// Every producer pushes batches of 8 identical elements
+	let producers: Vec<_> = (0..PRODUCERS).map(|producer| {
+		let queue = queue.clone();
+		thread::spawn(move || for _ in 0..BATCHES {
+			queue.push_batch_blocking(vec![producer; 8], None).unwrap();
+		})
+	}).collect();
+	let consumers: Vec<_> = (0..2).map(|_| {
+		let queue = queue.clone();
+		thread::spawn(move || {
+			let mut batches = 0;
+			while let Some(batch) = queue.pop_batch_blocking(8, Some(Duration::from_millis(500))) {
+				assert!(batch.iter().all(|element| *element == batch[0]), "A batch was interleaved");
+				batches += 1;
+			}
+			batches
+		})
+	}).collect();
+	
+	producers.into_iter().for_each(|producer| producer.join().unwrap());
+	let batches: usize = consumers.into_iter().map(|consumer| consumer.join().unwrap()).sum();
+	assert_eq!(batches, PRODUCERS * BATCHES);
+}
@@ -0,0 +1,129 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::io::{ Read, Write };
+
+
+#[test]
+fn test_msb_first() {
+	let mut queue = BitQueue::new(BitOrder::MsbFirst);
+	queue.push_bits(0b101, 3).unwrap();
+	queue.push_bits(0b1_1110_0001, 9).unwrap();
+	queue.push_bits(0xa, 4).unwrap();
+	assert_eq!(queue.len_bits(), 16);
+	assert!(queue.is_aligned());
+	
+	let bytes = queue.clone().into_bytes().unwrap();
+	assert_eq!(&bytes[..], &[0b1011_1110, 0b0001_1010]);
+	
+	assert_eq!(queue.pop_bits(3), Some(0b101));
+	assert_eq!(queue.pop_bits(9), Some(0b1_1110_0001));
+	assert_eq!(queue.pop_bits(4), Some(0xa));
+	assert!(queue.is_empty());
+}
+
+#[test]
+fn test_lsb_first() {
+	let mut queue = BitQueue::new(BitOrder::LsbFirst);
+	queue.push_bits(0b101, 3).unwrap();
+	queue.push_bits(0b1_1111_0000, 9).unwrap();
+	queue.push_bits(0xa, 4).unwrap();
+	
+	let bytes = queue.clone().into_bytes().unwrap();
+	assert_eq!(&bytes[..], &[0b1000_0101, 0b1010_1111]);
+	
+	assert_eq!(queue.pop_bits(3), Some(0b101));
+	assert_eq!(queue.pop_bits(9), Some(0b1_1111_0000));
+	assert_eq!(queue.pop_bits(4), Some(0xa));
+	assert!(queue.is_empty());
+}
+
+#[test]
+fn test_partial_bytes() {
+	let cases = [(BitOrder::MsbFirst, 0x1234_5678_9abc_def0, 1, 0b011_1111), (BitOrder::LsbFirst, 0x091a_2b3c_4d5e_6f78, 0, 0b111_1111)];
+	for &(order, value, bit, rest) in cases.iter() {
+		let mut queue = BitQueue::new(order);
+		queue.push_bits(0x1234_5678_9abc_def0, 64).unwrap();
+		queue.push_bits(0b10, 2).unwrap();
+		assert_eq!(queue.pop_bits(1), Some(0));
+		assert_eq!(queue.pop_bits(63), Some(value));
+		
+		// Read from the partially filled last byte and continue writing into it
+		assert_eq!(queue.peek_bits(2), Some(0b10));
+		assert_eq!(queue.pop_bits(1), Some(bit));
+		queue.push_bits(0x3f, 6).unwrap();
+		assert_eq!(queue.len_bits(), 7);
+		assert!(!queue.is_aligned());
+		assert_eq!(queue.pop_bits(7), Some(rest));
+	}
+}
+
+#[test]
+fn test_align() {
+	let mut queue = BitQueue::new(BitOrder::MsbFirst);
+	queue.push_bits(0b1, 1).unwrap();
+	assert_eq!(queue.pad_to_byte(), Ok(7));
+	assert_eq!(queue.pad_to_byte(), Ok(0));
+	queue.push_bits(0xff, 8).unwrap();
+	
+	assert_eq!(queue.pop_bits(2), Some(0b10));
+	assert_eq!(queue.skip_to_byte(), 6);
+	assert_eq!(queue.skip_to_byte(), 0);
+	assert_eq!(queue.into_bytes().unwrap().pop(), Ok(0xff));
+}
+
+#[test]
+fn test_skip_to_byte_tail() {
+	let mut queue = BitQueue::new(BitOrder::MsbFirst);
+	queue.push_bits(0b101, 3).unwrap();
+	
+	// Skipping the partially filled last byte leaves an empty, aligned queue
+	assert_eq!(queue.skip_to_byte(), 3);
+	assert!(queue.is_empty() && queue.is_aligned());
+	queue.write_all(b"!").unwrap();
+	let mut buf = [0; 1];
+	queue.read_exact(&mut buf).unwrap();
+	assert_eq!(&buf, b"!");
+}
+
+#[test]
+fn test_read_write() {
+	let mut queue = BitQueue::from_bytes(SliceQueue::from(b"Test".as_ref()), BitOrder::MsbFirst);
+	queue.write_all(b"ing").unwrap();
+	
+	let mut buf = [0; 7];
+	queue.read_exact(&mut buf).unwrap();
+	assert_eq!(&buf, b"Testing");
+}
+
+
+#[test]
+fn test_limit() {
+	let mut queue = BitQueue::with_limit(BitOrder::LsbFirst, 1);
+	queue.push_bits(0x7f, 7).unwrap();
+	assert_eq!(queue.remaining_bits(), 1);
+	assert_eq!(queue.push_bits(0b11, 2), Err(0b11));
+	queue.push_bits(0b1, 1).unwrap();
+	queue.push_bits(0b1, 1).unwrap_err();
+	assert_eq!(queue.len_bits(), 8);
+}
+
+#[test]
+fn test_not_enough_bits() {
+	let mut queue = BitQueue::new(BitOrder::MsbFirst);
+	queue.push_bits(0b101, 3).unwrap();
+	assert_eq!(queue.pop_bits(4), None);
+	assert_eq!(queue.len_bits(), 3);
+}
+
+#[test]
+fn test_unaligned_io() {
+	let mut queue = BitQueue::new(BitOrder::MsbFirst);
+	queue.push_bits(0b1, 1).unwrap();
+	assert!(queue.write(b"Testolope").is_err());
+	assert!(queue.clone().into_bytes().is_err());
+	
+	queue.pad_to_byte().unwrap();
+	queue.write_all(b"Testolope").unwrap();
+	queue.pop_bits(1).unwrap();
+	assert!(queue.read(&mut [0; 4]).is_err());
+}
@@ -0,0 +1,116 @@
+extern crate slice_queue;
+use slice_queue::*;
+
+
+#[test]
+fn test_broadcast() {
+	let mut queue = BroadcastSliceQueue::new();
+	queue.push_from(b"Lost").unwrap();
+	assert!(queue.is_empty());
+	
+	// Every subscriber receives the elements pushed after subscribing
+	let a = queue.subscribe();
+	queue.push_from(b"Test").unwrap();
+	let b = queue.subscribe();
+	queue.push_n(b"olope".to_vec()).unwrap();
+	queue.push(b'!').unwrap();
+	assert_eq!((queue.len(), queue.subscriber_count(), queue.pushed_total()), (10, 2, 14));
+	
+	{
+		let mut reader = queue.reader(a).unwrap();
+		assert_eq!(reader.id(), a);
+		assert_eq!(reader.len(), 10);
+		assert_eq!(reader.peek(), Some(&b'T'));
+		assert_eq!(reader.pop_n(4).unwrap(), b"Test");
+		assert_eq!(reader.position(), 8);
+	}
+	{
+		let mut reader = queue.reader(b).unwrap();
+		assert_eq!(reader.peek_n(6).unwrap(), b"olope!");
+		assert_eq!(reader.pop(), Ok(b'o'));
+		reader.drop_n(2).unwrap();
+	}
+	
+	// Validate that only the elements needed by the slowest subscriber are retained
+	assert_eq!(queue.len(), 6);
+	{
+		let mut reader = queue.reader(a).unwrap();
+		let mut buf = [0; 8];
+		assert_eq!(reader.pop_into(&mut buf), Err(6));
+		assert_eq!(&buf[..6], b"olope!");
+		assert!(reader.is_empty());
+	}
+	assert_eq!(queue.len(), 3);
+	
+	// Validate that unsubscribing frees the elements
+	assert!(queue.unsubscribe(b));
+	assert!(!queue.unsubscribe(b));
+	assert!(queue.reader(b).is_none());
+	assert!(queue.is_empty());
+}
+
+#[test]
+fn test_broadcast_reject() {
+	let mut queue = BroadcastSliceQueue::with_limit(4, OverflowPolicy::Reject);
+	let (a, b) = (queue.subscribe(), queue.subscribe());
+	assert_eq!(queue.push_from(b"Testolope"), Err(4));
+	assert_eq!(queue.push(b'!'), Err(b'!'));
+	
+	queue.reader(a).unwrap().drop_n(4).unwrap();
+	assert_eq!(queue.remaining(), 0);
+	queue.reader(b).unwrap().drop_n(2).unwrap();
+	assert_eq!(queue.push_n(b"olo".to_vec()), Err(b"o".to_vec()));
+	assert_eq!(queue.reader(a).unwrap().pop_n(4), Err(b"ol".to_vec()));
+	assert_eq!(queue.reader(b).unwrap().pop_n(4).unwrap(), b"stol");
+}
+
+#[test]
+fn test_broadcast_drop_slowest() {
+	let mut queue = BroadcastSliceQueue::with_limit(4, OverflowPolicy::DropSlowest);
+	let (a, b) = (queue.subscribe(), queue.subscribe());
+	queue.push_from(b"Test").unwrap();
+	queue.reader(a).unwrap().drop_n(2).unwrap();
+	
+	// `b` is the slowest subscriber and is dropped
+	queue.push_from(b"ol").unwrap();
+	assert!(!queue.is_subscribed(b));
+	assert_eq!(queue.reader(a).unwrap().pop_n(4).unwrap(), b"stol");
+	
+	// Validate that all elements are freed if all subscribers are dropped
+	queue.push_from(b"Testolope").unwrap();
+	assert_eq!((queue.subscriber_count(), queue.len()), (0, 0));
+}
+
+#[test]
+fn test_broadcast_lag() {
+	let mut queue = BroadcastSliceQueue::with_limit(4, OverflowPolicy::Lag);
+	assert_eq!(queue.overflow_policy(), OverflowPolicy::Lag);
+	let (a, b) = (queue.subscribe(), queue.subscribe());
+	queue.push_from(b"Test").unwrap();
+	queue.reader(a).unwrap().drop_n(3).unwrap();
+	
+	// `b` skips the elements that do not fit
+	queue.push_from(b"olope").unwrap();
+	assert_eq!(queue.len(), 4);
+	{
+		let mut reader = queue.reader(b).unwrap();
+		assert_eq!(reader.lagged(), 5);
+		assert_eq!(reader.pop_n(4).unwrap(), b"lope");
+	}
+	{
+		let mut reader = queue.reader(a).unwrap();
+		assert_eq!(reader.lagged(), 2);
+		assert_eq!(reader.pop_n(8), Err(b"lope".to_vec()));
+	}
+	
+	queue.set_overflow_policy(OverflowPolicy::Reject);
+	queue.set_limit(1);
+	assert_eq!(queue.push_from(b"!?"), Err(1));
+}
+
+
+#[test]
+#[should_panic(expected = "`limit` is `0`")]
+fn test_broadcast_limit_zero() {
+	BroadcastSliceQueue::<u8>::with_limit(0, OverflowPolicy::Reject);
+}
@@ -0,0 +1,99 @@
+extern crate slice_queue;
+use slice_queue::*;
+
+
+#[test]
+fn test_push_pop() {
+	let mut slice_queue = SliceQueue::new();
+	slice_queue.push_u8(0x01).unwrap();
+	slice_queue.push_u16_be(0x0203).unwrap();
+	slice_queue.push_u32_le(0x07060504).unwrap();
+	slice_queue.push_i64_be(-2).unwrap();
+	slice_queue.push_f32_le(1.5).unwrap();
+	slice_queue.push_f64_be(-0.25).unwrap();
+	assert_eq!(&slice_queue[..7], b"\x01\x02\x03\x04\x05\x06\x07");
+	
+	assert_eq!(slice_queue.pop_u8().unwrap(), 0x01);
+	assert_eq!(slice_queue.pop_u16_be().unwrap(), 0x0203);
+	assert_eq!(slice_queue.pop_u32_le().unwrap(), 0x07060504);
+	assert_eq!(slice_queue.pop_i64_be().unwrap(), -2);
+	assert_eq!(slice_queue.pop_f32_le().unwrap(), 1.5);
+	assert_eq!(slice_queue.pop_f64_be().unwrap(), -0.25);
+	assert!(slice_queue.is_empty());
+}
+#[test]
+fn test_peek_at() {
+	let slice_queue = SliceQueue::from(b"\x00\x01\x02\x03\x04\x05\x06\x07\x08".as_ref());
+	assert_eq!(slice_queue.peek_u16_le_at(1).unwrap(), 0x0201);
+	assert_eq!(slice_queue.peek_u32_be_at(5).unwrap(), 0x05060708);
+	assert_eq!(slice_queue.peek_i8_at(8).unwrap(), 8);
+	assert_eq!(slice_queue.peek_bytes_at(2, 3).unwrap(), b"\x02\x03\x04");
+	assert_eq!(slice_queue.len(), 9);
+}
+
+#[test]
+fn test_varint() {
+	let mut slice_queue = SliceQueue::new();
+	slice_queue.push_varint(0).unwrap();
+	slice_queue.push_varint(300).unwrap();
+	slice_queue.push_varint(u64::MAX).unwrap();
+	slice_queue.push_varint_i64(-1).unwrap();
+	slice_queue.push_varint_i64(i64::MIN).unwrap();
+	assert_eq!(&slice_queue[..3], b"\x00\xac\x02");
+	
+	assert_eq!(slice_queue.peek_varint().unwrap(), (0, 1));
+	assert_eq!(slice_queue.pop_varint_u64().unwrap(), 0);
+	assert_eq!(slice_queue.pop_varint_u64().unwrap(), 300);
+	assert_eq!(slice_queue.pop_varint_u64().unwrap(), u64::MAX);
+	assert_eq!(slice_queue.peek_varint().unwrap(), (1, 1));
+	assert_eq!(slice_queue.pop_varint_i64().unwrap(), -1);
+	assert_eq!(slice_queue.pop_varint_i64().unwrap(), i64::MIN);
+	
+	// Non-minimal encodings are accepted
+	slice_queue.push_from(b"\x80\x00").unwrap();
+	assert_eq!(slice_queue.peek_varint().unwrap(), (0, 2));
+	assert_eq!(slice_queue.pop_varint_u64().unwrap(), 0);
+	assert!(slice_queue.is_empty());
+}
+
+
+#[test]
+fn test_pop_incomplete() {
+	let mut slice_queue = SliceQueue::from(b"\x01\x02\x03".as_ref());
+	assert_eq!(slice_queue.pop_u32_be().unwrap_err(), Incomplete{ needed: 4, available: 3 });
+	assert_eq!(slice_queue.peek_u16_be_at(2).unwrap_err(), Incomplete{ needed: 4, available: 3 });
+	assert_eq!(&slice_queue[..], b"\x01\x02\x03");
+}
+#[test]
+fn test_push_limit() {
+	let mut slice_queue = SliceQueue::with_limit(5);
+	slice_queue.push_u32_be(7).unwrap();
+	assert_eq!(slice_queue.push_u16_le(0x0102).unwrap_err(), 0x0102);
+	assert_eq!(slice_queue.len(), 4);
+}
+
+#[test]
+fn test_varint_incomplete() {
+	let mut slice_queue = SliceQueue::from(b"\xac".as_ref());
+	assert_eq!(slice_queue.pop_varint_u64().unwrap_err(), VarintError::Incomplete);
+	assert_eq!(&slice_queue[..], b"\xac");
+	
+	// Complete the varint
+	slice_queue.push(0x02).unwrap();
+	assert_eq!(slice_queue.pop_varint_u64().unwrap(), 300);
+}
+#[test]
+fn test_varint_invalid() {
+	let mut slice_queue = SliceQueue::from(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02".as_ref());
+	assert_eq!(slice_queue.pop_varint_u64().unwrap_err(), VarintError::Overflow);
+	
+	let mut slice_queue = SliceQueue::from(b"\x80\x80\x80\x80\x80\x80\x80\x80\x80\x80\x00".as_ref());
+	assert_eq!(slice_queue.pop_varint_i64().unwrap_err(), VarintError::Overlong);
+	assert_eq!(slice_queue.len(), 11);
+}
+#[test]
+fn test_varint_limit() {
+	let mut slice_queue = SliceQueue::with_limit(2);
+	assert_eq!(slice_queue.push_varint(1 << 14).unwrap_err(), 1 << 14);
+	assert!(slice_queue.is_empty());
+}
@@ -0,0 +1,407 @@
+extern crate slice_queue;
+use slice_queue::{ *, codec::* };
+
+
+#[test]
+fn test_length_prefixed_fixed() {
+	let headers = [
+		(LengthHeader::U8, b"\x09".as_ref()),
+		(LengthHeader::U16(ByteOrder::BigEndian), b"\x00\x09".as_ref()),
+		(LengthHeader::U16(ByteOrder::LittleEndian), b"\x09\x00".as_ref()),
+		(LengthHeader::U32(ByteOrder::BigEndian), b"\x00\x00\x00\x09".as_ref()),
+		(LengthHeader::U64(ByteOrder::LittleEndian), b"\x09\x00\x00\x00\x00\x00\x00\x00".as_ref())
+	];
+	for (header, encoded) in headers.iter() {
+		let (mut codec, mut slice_queue) = (LengthPrefixedCodec::new(*header), SliceQueue::new());
+		
+		// Encode a frame and validate the header
+		codec.encode(b"Testolope".as_ref(), &mut slice_queue).unwrap();
+		assert_eq!(&slice_queue[..encoded.len()], *encoded);
+		assert_eq!(&slice_queue[encoded.len()..], b"Testolope");
+		
+		// Decode the frame
+		assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"Testolope");
+		assert!(slice_queue.is_empty());
+	}
+}
+#[test]
+fn test_length_prefixed_varint() {
+	let (mut codec, mut slice_queue) = (LengthPrefixedCodec::new(LengthHeader::Varint), SliceQueue::new());
+	let payload = vec![7u8; 300];
+	
+	// Encode a frame with a two-byte header and decode it
+	codec.encode(payload.as_slice(), &mut slice_queue).unwrap();
+	assert_eq!(&slice_queue[..2], b"\xac\x02");
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), payload);
+}
+#[test]
+fn test_length_prefixed_adjustments() {
+	let mut codec = LengthPrefixedCodec::new(LengthHeader::U16(ByteOrder::BigEndian));
+	codec.set_length_includes_header(true);
+	codec.set_length_offset(1);
+	
+	// Encode a frame; the header value is `4 + 2 + 1`
+	let mut slice_queue = SliceQueue::new();
+	codec.encode(b"Test".as_ref(), &mut slice_queue).unwrap();
+	assert_eq!(&slice_queue[..], b"\x00\x07Test");
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"Test");
+}
+#[test]
+fn test_length_prefixed_partial() {
+	let mut codec = LengthPrefixedCodec::new(LengthHeader::U32(ByteOrder::BigEndian));
+	let mut slice_queue = SliceQueue::new();
+	
+	// Push the frame byte by byte and validate that nothing is consumed until it is complete
+	for (i, b) in b"\x00\x00\x00\x04Test\x00".iter().enumerate() {
+		slice_queue.push(*b).unwrap();
+		match i {
+			7 => assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"Test"),
+			_ => assert!(codec.decode(&mut slice_queue).unwrap().is_none())
+		}
+	}
+	assert_eq!(&slice_queue[..], b"\x00");
+}
+
+
+#[test]
+fn test_length_prefixed_too_large() {
+	// Exceed the codec's maximum
+	let mut codec = LengthPrefixedCodec::new(LengthHeader::U8);
+	codec.set_max_frame_len(3);
+	let mut slice_queue = SliceQueue::from(b"\x04Test".as_ref());
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 4, max: 3 });
+	assert_eq!(codec.encode(b"Test".as_ref(), &mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 4, max: 3 });
+	
+	// Exceed the queue's limit
+	let mut codec = LengthPrefixedCodec::new(LengthHeader::U8);
+	let mut slice_queue = SliceQueue::with_limit(4);
+	slice_queue.push_from(b"\x04Tes").unwrap();
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 5, max: 4 });
+	
+	// Exceed the header's capacity
+	let mut slice_queue = SliceQueue::new();
+	assert_eq!(codec.encode(vec![0; 256].as_slice(), &mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 256, max: 255 });
+}
+#[test]
+fn test_length_prefixed_no_space() {
+	let mut codec = LengthPrefixedCodec::new(LengthHeader::U8);
+	let mut slice_queue = SliceQueue::with_limit(7);
+	
+	// Validate that nothing is pushed if the frame does not fit
+	codec.encode(b"Test".as_ref(), &mut slice_queue).unwrap();
+	assert_eq!(codec.encode(b"Test".as_ref(), &mut slice_queue).unwrap_err(), CodecError::NoSpace{ needed: 5, remaining: 2 });
+	assert_eq!(&slice_queue[..], b"\x04Test");
+}
+#[test]
+fn test_length_prefixed_malformed() {
+	// A length that is smaller than the header
+	let mut codec = LengthPrefixedCodec::new(LengthHeader::U8);
+	codec.set_length_includes_header(true);
+	let mut slice_queue = SliceQueue::from(b"\x00".as_ref());
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::Malformed("length field is too small"));
+	
+	// A varint that overflows 64 bits
+	let mut codec = LengthPrefixedCodec::new(LengthHeader::Varint);
+	let mut slice_queue = SliceQueue::from(b"\xff\xff\xff\xff\xff\xff\xff\xff\xff\x02".as_ref());
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::Malformed("varint overflows 64 bits"));
+}
+
+
+#[test]
+fn test_cobs() {
+	let vectors: [(&[u8], &[u8]); 5] = [
+		(b"", b"\x01\x00"),
+		(b"\x00", b"\x01\x01\x00"),
+		(b"\x00\x00", b"\x01\x01\x01\x00"),
+		(b"\x11\x22\x00\x33", b"\x03\x11\x22\x02\x33\x00"),
+		(b"\x11\x00\x00\x00", b"\x02\x11\x01\x01\x01\x00")
+	];
+	for (decoded, encoded) in vectors.iter() {
+		let (mut codec, mut slice_queue) = (CobsCodec::new(), SliceQueue::new());
+		codec.encode(*decoded, &mut slice_queue).unwrap();
+		assert_eq!(&slice_queue[..], *encoded);
+		assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), *decoded);
+		assert!(slice_queue.is_empty());
+	}
+}
+#[test]
+fn test_cobs_max_block() {
+	let (mut codec, mut slice_queue) = (CobsCodec::new(), SliceQueue::new());
+	
+	// 254 non-zero bytes fit into exactly one block
+	let decoded: Vec<u8> = (1..=254).collect();
+	codec.encode(decoded.as_slice(), &mut slice_queue).unwrap();
+	assert_eq!(slice_queue.len(), 256);
+	assert_eq!(slice_queue[0], 0xff);
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), decoded);
+	
+	// 255 non-zero bytes need a second block
+	let decoded: Vec<u8> = (0..255).map(|i| (i % 254) as u8 + 1).collect();
+	codec.encode(decoded.as_slice(), &mut slice_queue).unwrap();
+	assert_eq!(&slice_queue[254..], b"\xfe\x02\x01\x00");
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), decoded);
+}
+#[test]
+fn test_cobs_stream() {
+	let mut codec = CobsCodec::new();
+	let mut slice_queue = SliceQueue::from(b"\x00\x03Te\x02s\x00\x02!".as_ref());
+	
+	// Skip the leading delimiter, decode the first frame and wait for the second one
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"Te\x00s");
+	assert!(codec.decode(&mut slice_queue).unwrap().is_none());
+	assert_eq!(&slice_queue[..], b"\x02!");
+	
+	slice_queue.push(0x00).unwrap();
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"!");
+}
+#[test]
+fn test_slip() {
+	let (mut codec, mut slice_queue) = (SlipCodec::new(), SliceQueue::new());
+	codec.encode(b"Te\xc0st\xdb".as_ref(), &mut slice_queue).unwrap();
+	assert_eq!(&slice_queue[..], b"Te\xdb\xdcst\xdb\xdd\xc0");
+	
+	// Push a partial frame and decode the frames
+	slice_queue.push_from(b"\xc0!!").unwrap();
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"Te\xc0st\xdb");
+	assert!(codec.decode(&mut slice_queue).unwrap().is_none());
+	assert_eq!(&slice_queue[..], b"!!");
+}
+
+
+#[test]
+fn test_cobs_resync() {
+	let mut codec = CobsCodec::new();
+	let mut slice_queue = SliceQueue::from(b"\x05Te\x00\x02!\x00".as_ref());
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::Malformed("COBS block exceeds the frame"));
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"!");
+}
+#[test]
+fn test_cobs_too_large() {
+	// Exceed the queue's limit without a delimiter
+	let mut codec = CobsCodec::new();
+	let mut slice_queue = SliceQueue::with_limit(4);
+	slice_queue.push_from(b"\x05Tes").unwrap();
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 4, max: 4 });
+	assert!(slice_queue.is_empty());
+	
+	// Exceed the codec's maximum
+	codec.set_max_frame_len(2);
+	slice_queue.push_from(b"\x04Te!").unwrap();
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 4, max: 3 });
+}
+#[test]
+fn test_slip_resync() {
+	let mut codec = SlipCodec::new();
+	let mut slice_queue = SliceQueue::from(b"Te\xdbst\xc0!\xdb\xc0Test\xc0".as_ref());
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::Malformed("invalid SLIP escape sequence"));
+	assert_eq!(codec.decode(&mut slice_queue).unwrap_err(), CodecError::Malformed("truncated SLIP escape sequence"));
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"Test");
+}
+
+
+#[test]
+fn test_http_request_head() {
+	let mut decoder = HttpHeadDecoder::new();
+	let mut slice_queue = SliceQueue::from(b"\r\nGET /index.html HTTP/1.1\r\nHost: example.com\r\nX-Empty:\r\n\r\nBody".as_ref());
+	
+	let host = decoder.decode_with(&mut slice_queue, |head| {
+		assert_eq!(head.start_line, StartLine::Request{ method: "GET", target: "/index.html", version: "HTTP/1.1" });
+		assert_eq!(head.headers.len(), 2);
+		assert_eq!(head.header("x-empty").unwrap(), b"");
+		head.header("HOST").unwrap().to_vec()
+	}).unwrap().unwrap();
+	assert_eq!(host, b"example.com");
+	assert_eq!(&slice_queue[..], b"Body");
+}
+#[test]
+fn test_http_response_head() {
+	let mut decoder = HttpHeadDecoder::new();
+	let mut slice_queue = SliceQueue::from(b"HTTP/1.1 404 Not Found\r\nContent-Length:  0 \r\n\r".as_ref());
+	
+	// Decode the incomplete and the complete head
+	assert!(decoder.decode_with(&mut slice_queue, |_| ()).unwrap().is_none());
+	slice_queue.push(b'\n').unwrap();
+	decoder.decode_with(&mut slice_queue, |head| {
+		assert_eq!(head.start_line, StartLine::Response{ version: "HTTP/1.1", status: 404, reason: b"Not Found" });
+		assert_eq!(head.header("content-length").unwrap(), b"0");
+	}).unwrap().unwrap();
+	assert!(slice_queue.is_empty());
+}
+#[test]
+fn test_http_chunked() {
+	let mut decoder = ChunkedDecoder::new();
+	let mut slice_queue = SliceQueue::from(b"4\r\nTest\r\n5;ext=1\r\nolope\r\n0\r\nExpires: never\r\n\r".as_ref());
+	
+	assert_eq!(decoder.decode(&mut slice_queue).unwrap().unwrap(), ChunkedItem::Chunk(b"Test".to_vec()));
+	assert_eq!(decoder.decode(&mut slice_queue).unwrap().unwrap(), ChunkedItem::Chunk(b"olope".to_vec()));
+	assert!(decoder.decode(&mut slice_queue).unwrap().is_none());
+	
+	// Complete the trailers
+	slice_queue.push_from(b"\nNext").unwrap();
+	assert_eq!(
+		decoder.decode(&mut slice_queue).unwrap().unwrap(),
+		ChunkedItem::End(vec![("Expires".to_string(), b"never".to_vec())])
+	);
+	assert!(decoder.is_done());
+	assert!(decoder.decode(&mut slice_queue).unwrap().is_none());
+	assert_eq!(&slice_queue[..], b"Next");
+}
+
+
+#[test]
+fn test_http_head_too_large() {
+	// Exceed the queue's limit
+	let mut decoder = HttpHeadDecoder::new();
+	let mut slice_queue = SliceQueue::with_limit(16);
+	slice_queue.push_from(b"GET / HTTP/1.1\r\nX").unwrap_err();
+	assert_eq!(decoder.decode_with(&mut slice_queue, |_| ()).unwrap_err(), CodecError::TooLarge{ size: 16, max: 16 });
+	
+	// Exceed the decoder's maximum
+	decoder.set_max_head_len(8);
+	let mut slice_queue = SliceQueue::from(b"GET / HTTP/1.1\r\n\r\n".as_ref());
+	assert_eq!(decoder.decode_with(&mut slice_queue, |_| ()).unwrap_err(), CodecError::TooLarge{ size: 18, max: 8 });
+}
+#[test]
+fn test_http_head_malformed() {
+	let heads: [(&[u8], &str); 4] = [
+		(b"GET /\r\n\r\n", "invalid start line"),
+		(b"HTTP/1.1 20 OK\r\n\r\n", "invalid status code"),
+		(b"GET / HTTP/1.1\r\nHost example.com\r\n\r\n", "header line without colon"),
+		(b"GET / HTTP/1.1\r\nHost: a\r\n b\r\n\r\n", "obsolete header line folding")
+	];
+	for (head, error) in heads.iter() {
+		let mut slice_queue = SliceQueue::from(*head);
+		assert_eq!(HttpHeadDecoder::new().decode_with(&mut slice_queue, |_| ()).unwrap_err(), CodecError::Malformed(error));
+		assert_eq!(&slice_queue[..], *head);
+	}
+}
+#[test]
+fn test_http_chunked_too_large() {
+	let mut decoder = ChunkedDecoder::new();
+	decoder.set_max_chunk_len(4);
+	
+	let mut slice_queue = SliceQueue::from(b"5\r\nolope\r\n".as_ref());
+	assert_eq!(decoder.decode(&mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 5, max: 4 });
+	
+	let mut slice_queue = SliceQueue::from(b"x\r\n".as_ref());
+	assert_eq!(decoder.decode(&mut slice_queue).unwrap_err(), CodecError::Malformed("invalid chunk size"));
+}
+
+
+#[test]
+fn test_resp() {
+	let values = [
+		(RespValue::SimpleString(b"OK".to_vec()), b"+OK\r\n".as_ref()),
+		(RespValue::Error(b"ERR unknown".to_vec()), b"-ERR unknown\r\n".as_ref()),
+		(RespValue::Integer(-42), b":-42\r\n".as_ref()),
+		(RespValue::BulkString(None), b"$-1\r\n".as_ref()),
+		(RespValue::BulkString(Some(b"Te\r\nst".to_vec())), b"$6\r\nTe\r\nst\r\n".as_ref()),
+		(RespValue::Array(Some(vec![
+			RespValue::Integer(7),
+			RespValue::Array(Some(vec![RespValue::BulkString(Some(Vec::new()))]))
+		])), b"*2\r\n:7\r\n*1\r\n$0\r\n\r\n".as_ref()),
+		(RespValue::Null, b"_\r\n".as_ref()),
+		(RespValue::Boolean(true), b"#t\r\n".as_ref()),
+		(RespValue::Double(1.5), b",1.5\r\n".as_ref()),
+		(RespValue::VerbatimString(*b"txt", b"Test".to_vec()), b"=8\r\ntxt:Test\r\n".as_ref()),
+		(RespValue::Map(vec![(RespValue::SimpleString(b"key".to_vec()), RespValue::Boolean(false))]), b"%1\r\n+key\r\n#f\r\n".as_ref())
+	];
+	for (value, encoded) in values.iter() {
+		let (mut codec, mut slice_queue) = (RespCodec::new(), SliceQueue::new());
+		codec.encode(value, &mut slice_queue).unwrap();
+		assert_eq!(&slice_queue[..], *encoded);
+		assert_eq!(&codec.decode(&mut slice_queue).unwrap().unwrap(), value);
+		assert!(slice_queue.is_empty());
+	}
+}
+#[test]
+fn test_resp_partial() {
+	let mut codec = RespCodec::new();
+	let mut slice_queue = SliceQueue::new();
+	
+	// Push the value byte by byte and validate that nothing is consumed until it is complete
+	let encoded = b"*2\r\n$4\r\nTest\r\n:7\r\n";
+	for (i, b) in encoded.iter().enumerate() {
+		slice_queue.push(*b).unwrap();
+		match i + 1 == encoded.len() {
+			true => assert_eq!(
+				codec.decode(&mut slice_queue).unwrap().unwrap(),
+				RespValue::Array(Some(vec![RespValue::BulkString(Some(b"Test".to_vec())), RespValue::Integer(7)]))
+			),
+			false => assert!(codec.decode(&mut slice_queue).unwrap().is_none())
+		}
+	}
+	assert!(slice_queue.is_empty());
+}
+#[test]
+fn test_netstring() {
+	let (mut codec, mut slice_queue) = (NetstringCodec::new(), SliceQueue::new());
+	codec.encode(b"Testolope".as_ref(), &mut slice_queue).unwrap();
+	codec.encode(b"".as_ref(), &mut slice_queue).unwrap();
+	assert_eq!(&slice_queue[..], b"9:Testolope,0:,");
+	
+	// Decode the netstrings and an incomplete one
+	slice_queue.push_from(b"4:Te").unwrap();
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"Testolope");
+	assert_eq!(codec.decode(&mut slice_queue).unwrap().unwrap(), b"");
+	assert!(codec.decode(&mut slice_queue).unwrap().is_none());
+	assert_eq!(&slice_queue[..], b"4:Te");
+}
+
+
+#[test]
+fn test_resp_invalid() {
+	let values: [(&[u8], CodecError); 5] = [
+		(b"?\r\n", CodecError::Malformed("invalid RESP type")),
+		(b":x\r\n", CodecError::Malformed("invalid RESP integer")),
+		(b"$2\r\nTest\r\n", CodecError::Malformed("RESP bulk data does not end with CRLF")),
+		(b"$9\r\nTest", CodecError::TooLarge{ size: 9, max: 4 }),
+		(b"*1\r\n*1\r\n*1\r\n", CodecError::TooLarge{ size: 3, max: 2 })
+	];
+	for (encoded, error) in values.iter() {
+		let mut codec = RespCodec::new();
+		codec.set_max_bulk_len(4);
+		codec.set_max_depth(2);
+		
+		let mut slice_queue = SliceQueue::from(*encoded);
+		assert_eq!(&codec.decode(&mut slice_queue).unwrap_err(), error);
+		assert_eq!(&slice_queue[..], *encoded);
+	}
+}
+#[test]
+fn test_resp_encode_invalid() {
+	let values = [
+		(RespValue::SimpleString(b"OK\r\n+Injected".to_vec()), CodecError::Malformed("RESP simple value contains CR or LF")),
+		(RespValue::Error(b"ERR\n".to_vec()), CodecError::Malformed("RESP simple value contains CR or LF")),
+		(RespValue::BigNumber(b"1\r2".to_vec()), CodecError::Malformed("RESP simple value contains CR or LF")),
+		(RespValue::VerbatimString(*b"t:t", b"Test".to_vec()), CodecError::Malformed("invalid RESP verbatim format")),
+		(RespValue::Array(Some(vec![RespValue::SimpleString(b"\r\n".to_vec())])), CodecError::Malformed("RESP simple value contains CR or LF"))
+	];
+	for (value, error) in values.iter() {
+		let mut slice_queue = SliceQueue::new();
+		assert_eq!(&RespCodec::new().encode(value, &mut slice_queue).unwrap_err(), error);
+		assert!(slice_queue.is_empty());
+	}
+}
+#[test]
+fn test_resp_limit() {
+	let mut slice_queue = SliceQueue::with_limit(8);
+	slice_queue.push_from(b"+Testolope\r\n").unwrap_err();
+	assert_eq!(RespCodec::new().decode(&mut slice_queue).unwrap_err(), CodecError::TooLarge{ size: 8, max: 8 });
+}
+#[test]
+fn test_netstring_invalid() {
+	let netstrings: [(&[u8], CodecError); 4] = [
+		(b"04:Test,", CodecError::Malformed("netstring length has leading zeros")),
+		(b"4:Test;", CodecError::Malformed("netstring does not end with a comma")),
+		(b"x:", CodecError::Malformed("invalid netstring length")),
+		(b"9:Test", CodecError::TooLarge{ size: 9, max: 4 })
+	];
+	for (encoded, error) in netstrings.iter() {
+		let mut codec = NetstringCodec::new();
+		codec.set_max_len(4);
+		
+		let mut slice_queue = SliceQueue::from(*encoded);
+		assert_eq!(&codec.decode(&mut slice_queue).unwrap_err(), error);
+	}
+}
@@ -0,0 +1,202 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::{ env, fs::{ self, OpenOptions }, io::{ ErrorKind, Read, Write }, path::PathBuf, process };
+
+
+/// Creates a unique log path for `name` and removes stale files
+fn log_path(name: &str) -> PathBuf {
+	let path = env::temp_dir().join(format!("slice_queue-test-{}-{}.wal", process::id(), name));
+	let _ = fs::remove_file(&path);
+	path
+}
+
+
+#[test]
+fn test_durable_recover() {
+	let path = log_path("recover");
+	{
+		let mut queue = DurableQueue::create(&path).unwrap();
+		queue.push_from(b"Testolope").unwrap();
+		queue.write_all(b"!?").unwrap();
+		assert_eq!(queue.pop_n(4).unwrap(), b"Test");
+		assert_eq!(queue.drop_n(2), Ok(()));
+		
+		let mut buf = [0; 2];
+		queue.read_exact(&mut buf).unwrap();
+		assert_eq!(&buf, b"op");
+		queue.sync().unwrap();
+	}
+	
+	// Rebuild the unconsumed bytes and continue
+	let mut queue = DurableQueue::recover(&path).unwrap();
+	assert_eq!(queue.peek_n(usize::MAX).unwrap_err(), b"e!?");
+	queue.push_from(b"Testolope").unwrap();
+	assert_eq!(queue.drop_n(usize::MAX), Err(12));
+	drop(queue);
+	
+	assert!(DurableQueue::recover(&path).unwrap().is_empty());
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_limit() {
+	let path = log_path("limit");
+	let mut queue = DurableQueue::create(&path).unwrap();
+	queue.set_limit(4);
+	assert_eq!(queue.push_from(b"Testolope"), Err(4));
+	assert_eq!(queue.remaining(), 0);
+	assert_eq!(queue.push(b'!'), Err(b'!'));
+	drop(queue);
+	
+	assert_eq!(DurableQueue::recover(&path).unwrap().peek_n(usize::MAX).unwrap_err(), b"Test");
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_torn_tail() {
+	let path = log_path("torn_tail");
+	let mut queue = DurableQueue::create(&path).unwrap();
+	queue.push_from(b"Testolope").unwrap();
+	queue.push_from(b"!?").unwrap();
+	let log_len = queue.log_len();
+	drop(queue);
+	
+	// Simulate a torn write of the last record
+	OpenOptions::new().write(true).open(&path).unwrap().set_len(log_len - 1).unwrap();
+	let mut queue = DurableQueue::recover(&path).unwrap();
+	assert_eq!(queue.peek_n(usize::MAX).unwrap_err(), b"Testolope");
+	assert_eq!(queue.truncated_len(), 9 + 2 + 4 - 1);
+	
+	// Validate that new records are appended after the valid records
+	queue.push_from(b"?!").unwrap();
+	drop(queue);
+	assert_eq!(DurableQueue::recover(&path).unwrap().peek_n(usize::MAX).unwrap_err(), b"Testolope?!");
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_traits() {
+	let path = log_path("traits");
+	let mut queue = DurableQueue::create(&path).unwrap();
+	queue.push(b'T').unwrap();
+	queue.push_n(b"esto".to_vec()).unwrap();
+	assert_eq!(queue.push_in_place(7, |buf: &mut[u8]| -> Result<usize, ()> {
+		buf[..4].copy_from_slice(b"lope");
+		Ok(4)
+	}), Ok(4));
+	assert_eq!(queue.pop(), Ok(b'T'));
+	let mut buf = [0; 3];
+	queue.pop_into(&mut buf).unwrap();
+	assert_eq!(&buf, b"est");
+	
+	// Use the queue with a codec
+	queue.push_u16_be(0x0102).unwrap();
+	queue.drop_n(5).unwrap();
+	assert_eq!(queue.pop_u16_be(), Ok(0x0102));
+	drop(queue);
+	
+	assert!(DurableQueue::recover(&path).unwrap().is_empty());
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_compact() {
+	let path = log_path("compact");
+	let mut queue = DurableQueue::create(&path).unwrap();
+	queue.set_compaction_threshold(256);
+	for _ in 0..64 {
+		queue.push_from(b"Testolope").unwrap();
+		queue.drop_n(8).unwrap();
+	}
+	assert!(queue.log_len() <= 256);
+	assert_eq!(queue.len(), 64);
+	
+	// Compact explicitly and recover
+	queue.compact().unwrap();
+	assert_eq!(queue.log_len(), 5 + 9 + 64 + 4);
+	queue.push_from(b"!").unwrap();
+	drop(queue);
+	
+	let queue = DurableQueue::recover(&path).unwrap();
+	assert_eq!(queue.len(), 65);
+	assert_eq!(&queue.peek_n(usize::MAX).unwrap_err()[61..], b"ope!");
+	fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+fn test_durable_create_existing() {
+	let path = log_path("create_existing");
+	drop(DurableQueue::create(&path).unwrap());
+	assert_eq!(DurableQueue::create(&path).unwrap_err().kind(), ErrorKind::AlreadyExists);
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_invalid_magic() {
+	let path = log_path("invalid_magic");
+	fs::write(&path, b"Testolope").unwrap();
+	assert_eq!(DurableQueue::recover(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_corrupted_record() {
+	let path = log_path("corrupted_record");
+	let mut queue = DurableQueue::create(&path).unwrap();
+	queue.push_from(b"Testolope").unwrap();
+	queue.push_from(b"!?").unwrap();
+	drop(queue);
+	
+	// Flip a payload byte of the first record
+	let mut log = fs::read(&path).unwrap();
+	log[5 + 9] ^= 0xff;
+	fs::write(&path, &log).unwrap();
+	assert_eq!(DurableQueue::recover(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+	
+	// Validate that the log is not truncated
+	assert_eq!(fs::read(&path).unwrap(), log);
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_corrupted_tail() {
+	let path = log_path("corrupted_tail");
+	let mut queue = DurableQueue::create(&path).unwrap();
+	queue.push_from(b"Testolope").unwrap();
+	queue.push_from(b"!?").unwrap();
+	drop(queue);
+	
+	// A corrupted last record is treated like a torn write
+	let mut log = fs::read(&path).unwrap();
+	let last = log.len() - 5;
+	log[last] ^= 0xff;
+	fs::write(&path, &log).unwrap();
+	let queue = DurableQueue::recover(&path).unwrap();
+	assert_eq!(queue.peek_n(usize::MAX).unwrap_err(), b"Testolope");
+	assert_eq!(queue.truncated_len(), 9 + 2 + 4);
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_durable_compact_failed() {
+	let path = log_path("compact_failed");
+	let mut queue = DurableQueue::create(&path).unwrap();
+	queue.set_compaction_threshold(0);
+	
+	// Block the temporary compaction file with a directory
+	let mut compact_path = path.clone().into_os_string();
+	compact_path.push(".compact");
+	fs::create_dir_all(&compact_path).unwrap();
+	
+	// The consuming call succeeds and the compaction error is reported by the next `sync`-call
+	queue.push_from(b"Testolope").unwrap();
+	assert_eq!(queue.drop_n(9), Ok(()));
+	assert_eq!(queue.sync().unwrap_err().kind(), ErrorKind::AlreadyExists);
+	queue.sync().unwrap();
+	drop(queue);
+	
+	assert!(DurableQueue::recover(&path).unwrap().is_empty());
+	fs::remove_dir(&compact_path).unwrap();
+	fs::remove_file(&path).unwrap();
+}
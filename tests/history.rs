@@ -0,0 +1,88 @@
+extern crate slice_queue;
+use slice_queue::*;
+
+
+#[test]
+fn test_history() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	queue.set_history_limit(4);
+	assert_eq!(queue.history(0), Ok(b"".as_ref()));
+	
+	queue.pop().unwrap();
+	queue.pop_n(2).unwrap();
+	assert_eq!(queue.history(3), Ok(b"Tes".as_ref()));
+	
+	queue.pop_into(&mut [0; 2]).unwrap();
+	queue.drop_n(2).unwrap();
+	assert_eq!(queue.history_len(), 4);
+	assert_eq!(queue.history(4), Ok(b"tolo".as_ref()));
+	assert_eq!(queue.len(), 2);
+}
+
+#[test]
+fn test_copy_from_history() {
+	let mut queue = SliceQueue::new();
+	queue.set_history_limit(16);
+	queue.push_from(b"abc").unwrap();
+	queue.drop_n(3).unwrap();
+	queue.push_from(b"de").unwrap();
+	
+	// Reference the history and the stored elements
+	queue.copy_from_history(5, 4).unwrap();
+	assert_eq!(&queue[..], b"deabcd");
+	
+	// Overlapping references repeat the pattern
+	queue.copy_from_history(2, 5).unwrap();
+	assert_eq!(&queue[..], b"deabcdcdcdc");
+	queue.copy_from_history(1, 2).unwrap();
+	assert_eq!(&queue[..], b"deabcdcdcdccc");
+}
+
+#[test]
+fn test_history_shrink() {
+	let mut queue = SliceQueue::new();
+	queue.set_history_limit(4);
+	for byte in 0..64u8 {
+		queue.push(byte).unwrap();
+		queue.pop().unwrap();
+	}
+	assert_eq!(queue.history(4), Ok([60u8, 61, 62, 63].as_ref()));
+	
+	// Disabling the history releases it
+	queue.set_history_limit(0);
+	assert_eq!(queue.history_len(), 0);
+	queue.shrink_to_fit();
+	assert_eq!(queue.history(1), Err([].as_ref()));
+}
+
+
+#[test]
+fn test_history_disabled() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	queue.drop_n(4).unwrap();
+	assert_eq!(queue.history_len(), 0);
+	assert_eq!(queue.history(1), Err(b"".as_ref()));
+}
+
+#[test]
+fn test_history_too_short() {
+	let mut queue = SliceQueue::from(b"Testolope".as_ref());
+	queue.set_history_limit(2);
+	queue.drop_n(4).unwrap();
+	assert_eq!(queue.history(3), Err(b"st".as_ref()));
+}
+
+#[test]
+fn test_copy_from_history_limit() {
+	let mut queue = SliceQueue::with_limit(4);
+	queue.push_from(b"ab").unwrap();
+	assert_eq!(queue.copy_from_history(1, 3), Err(2));
+	assert_eq!(&queue[..], b"abbb");
+}
+
+#[test] #[should_panic(expected = "`distance` is outside of the history")]
+fn test_copy_from_history_distance() {
+	let mut queue = SliceQueue::from(b"ab".as_ref());
+	queue.set_history_limit(4);
+	queue.copy_from_history(3, 1).unwrap_or_default();
+}
@@ -0,0 +1,72 @@
+extern crate slice_queue;
+use slice_queue::*;
+
+
+#[test]
+fn test_push_pop_message() {
+	let mut message_queue = MessageQueue::new();
+	message_queue.push_message(b"Test").unwrap();
+	message_queue.push_message(b"").unwrap();
+	message_queue.push_message_n(b"olope".to_vec()).unwrap();
+	assert_eq!(message_queue.message_count(), 3);
+	assert_eq!(message_queue.len(), 9);
+	
+	// Peek at and pop the messages
+	assert_eq!(message_queue.peek_message().unwrap(), b"Test");
+	assert_eq!(message_queue.pop_message().unwrap(), b"Test");
+	assert_eq!(message_queue.pop_message().unwrap(), b"");
+	assert_eq!(message_queue.pop_message().unwrap(), b"olope");
+	assert!(message_queue.is_empty());
+	assert!(message_queue.pop_message().is_none());
+}
+#[test]
+fn test_pop_message_into() {
+	let mut message_queue = MessageQueue::new();
+	message_queue.push_message(b"Testolope").unwrap();
+	
+	// Pop into a too small and a sufficient buffer
+	let mut buf = [0u8; 16];
+	assert_eq!(message_queue.pop_message_into(&mut buf[..4]).unwrap_err(), 9);
+	assert_eq!(message_queue.message_count(), 1);
+	assert_eq!(message_queue.pop_message_into(&mut buf).unwrap(), 9);
+	assert_eq!(&buf[..9], b"Testolope");
+	assert_eq!(message_queue.pop_message_into(&mut buf).unwrap_err(), 0);
+}
+#[test]
+fn test_messages() {
+	let mut message_queue = MessageQueue::new();
+	message_queue.push_message(b"Te").unwrap();
+	message_queue.push_message(b"sto").unwrap();
+	message_queue.push_message(b"lope").unwrap();
+	
+	let messages: Vec<&[u8]> = message_queue.messages().collect();
+	assert_eq!(messages, [b"Te".as_ref(), b"sto", b"lope"]);
+	
+	message_queue.drop_messages(2).unwrap();
+	assert_eq!(message_queue.peek_message().unwrap(), b"lope");
+	assert_eq!(message_queue.drop_messages(2).unwrap_err(), 1);
+	assert_eq!(message_queue.len(), 0);
+}
+#[test]
+fn test_limits() {
+	let mut message_queue = MessageQueue::with_limits(8, 2);
+	
+	// Exceed the element limit
+	message_queue.push_message(b"Test").unwrap();
+	assert_eq!(message_queue.push_message(b"olope").unwrap_err(), b"olope");
+	
+	// Exceed the message limit
+	message_queue.push_message(b"!").unwrap();
+	assert_eq!(message_queue.remaining_messages(), 0);
+	assert_eq!(message_queue.push_message_n(b"!".to_vec()).unwrap_err(), b"!");
+	assert_eq!(message_queue.len(), 5);
+	
+	// Consume a message and push again
+	message_queue.pop_message().unwrap();
+	message_queue.push_message(b"!!").unwrap();
+	assert_eq!(message_queue.message_count(), 2);
+}
+#[test] #[should_panic(expected = "`message_limit` is `0`")]
+fn test_zero_message_limit() {
+	MessageQueue::<u8>::with_limits(1, 0);
+}
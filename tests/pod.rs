@@ -0,0 +1,119 @@
+#![cfg(feature = "unsafe_fast_code")]
+extern crate slice_queue;
+use slice_queue::*;
+
+
+#[test]
+fn test_push_pop_record() {
+	let mut queue = SliceQueue::new();
+	queue.push_record(&0x0102030405060708u64).unwrap();
+	queue.push_record(&[1u16, 2, 3]).unwrap();
+	queue.push_record(&-1.5f32).unwrap();
+	assert_eq!(queue.len(), 8 + 6 + 4);
+	
+	assert_eq!(queue.pop_record::<u64>().unwrap(), 0x0102030405060708);
+	assert_eq!(queue.pop_record::<[u16; 3]>().unwrap(), [1, 2, 3]);
+	assert_eq!(queue.pop_record::<f32>().unwrap(), -1.5);
+	assert!(queue.is_empty());
+}
+
+#[test]
+fn test_pop_record_unaligned() {
+	let mut queue = SliceQueue::new();
+	queue.push(0u8).unwrap();
+	queue.push_record(&0xdeadbeefu32).unwrap();
+	
+	queue.pop().unwrap();
+	assert_eq!(queue.pop_record::<u32>().unwrap(), 0xdeadbeef);
+}
+
+#[test]
+fn test_peek_record() {
+	let mut queue = SliceQueue::new();
+	queue.push_record(&[7u32, 9]).unwrap();
+	
+	// The backing is usually aligned by the allocator, but this is not guaranteed
+	match queue.peek_record::<[u32; 2]>() {
+		Ok(record) => assert_eq!(*record, [7, 9]),
+		Err(e) => assert_eq!(e, PodError::Misaligned{ align: 4 })
+	}
+	assert_eq!(queue.len(), 8);
+	assert_eq!(queue.peek_record::<u8>().unwrap(), &queue[0]);
+}
+
+#[test]
+fn test_cast() {
+	let mut queue = SliceQueue::with_limit(16);
+	queue.push_from(&[1u32, 2, 0xffffffff]).unwrap();
+	
+	// Reinterpret in place (same alignment)
+	let queue: SliceQueue<i32> = queue.cast().unwrap();
+	assert_eq!(&queue[..], &[1, 2, -1]);
+	assert_eq!(queue.limit(), 16);
+	
+	// Convert to bytes and back (different alignment)
+	let queue: SliceQueue<u8> = queue.cast().unwrap();
+	assert_eq!(queue.len(), 12);
+	assert_eq!(queue.limit(), 64);
+	let queue: SliceQueue<u32> = queue.cast().unwrap();
+	assert_eq!(&queue[..], &[1, 2, 0xffffffff]);
+	assert_eq!(queue.limit(), 16);
+}
+
+#[test]
+fn test_cast_offsets() {
+	let mut queue = SliceQueue::new();
+	queue.set_history_limit(2);
+	queue.push_from(&[1u32, 2, 3]).unwrap();
+	queue.pop().unwrap();
+	
+	// The absolute offsets and the history limit are converted to bytes
+	let mut queue: SliceQueue<u8> = queue.cast().unwrap();
+	assert_eq!(queue.consumed_total(), 4);
+	assert_eq!(queue.history_limit(), 8);
+	assert_eq!(queue.history_len(), 0);
+	assert_eq!(queue.get_abs(4), Some(&2));
+	
+	// 5 consumed bytes are not a whole amount of `u16`s, so the offsets restart
+	queue.drop_n(1).unwrap();
+	queue.push(0).unwrap();
+	let queue: SliceQueue<u16> = queue.cast().unwrap();
+	assert_eq!(queue.consumed_total(), 0);
+}
+
+
+#[test]
+fn test_push_record_limit() {
+	let mut queue = SliceQueue::with_limit(7);
+	assert_eq!(queue.push_record(&7u64), Err(7));
+	assert!(queue.is_empty());
+}
+
+#[test]
+fn test_record_incomplete() {
+	let mut queue = SliceQueue::new();
+	queue.push_from(&[1u8, 2, 3]).unwrap();
+	
+	assert_eq!(queue.pop_record::<u32>(), Err(Incomplete{ needed: 4, available: 3 }));
+	assert_eq!(queue.peek_record::<u32>(), Err(PodError::Incomplete(Incomplete{ needed: 4, available: 3 })));
+	assert_eq!(queue.len(), 3);
+}
+
+#[test]
+fn test_cast_invalid_len() {
+	let mut queue = SliceQueue::new();
+	queue.push_from(&[1u8, 2, 3]).unwrap();
+	
+	let queue = queue.cast::<u16>().unwrap_err();
+	assert_eq!(&queue[..], &[1, 2, 3]);
+}
+
+#[test]
+fn test_cast_zero_sized() {
+	let mut queue = SliceQueue::new();
+	queue.push_from(&[[0u8; 0]; 3]).unwrap();
+	
+	let queue = queue.cast::<u8>().unwrap_err();
+	assert_eq!(queue.len(), 3);
+	assert!(SliceQueue::from(b"ab".as_ref()).cast::<[u8; 0]>().is_err());
+}
@@ -0,0 +1,88 @@
+extern crate slice_queue;
+use slice_queue::*;
+
+
+#[test]
+fn test_priority_strict() {
+	let mut queue = PrioritySliceQueue::new(3);
+	assert_eq!(queue.lanes(), 3);
+	queue.push_from(2, b"bulk").unwrap();
+	queue.push_n(1, b"data".to_vec()).unwrap();
+	queue.push(0, b'!').unwrap();
+	assert_eq!((queue.len(), queue.lane_len(1)), (9, 4));
+	
+	// Higher lanes are drained first
+	assert_eq!(queue.pop(), Some(b'!'));
+	assert_eq!(queue.pop_n(2).unwrap(), b"da");
+	queue.push_from(0, b"ctl").unwrap();
+	
+	let mut buf = [0; 6];
+	queue.pop_into(&mut buf).unwrap();
+	assert_eq!(&buf, b"ctltab");
+	queue.drop_n(1).unwrap();
+	assert_eq!(queue.pop_n(8), Err(b"lk".to_vec()));
+	assert!(queue.is_empty());
+	assert_eq!(queue.pop(), None);
+	assert_eq!(queue.drop_n(1), Err(0));
+}
+
+#[test]
+fn test_priority_weighted() {
+	let mut queue = PrioritySliceQueue::new(2);
+	queue.set_scheduling(Scheduling::Weighted);
+	queue.set_weight(0, 3);
+	assert_eq!((queue.weight(0), queue.weight(1)), (3, 1));
+	queue.push_from(0, b"AAAAAAAA").unwrap();
+	queue.push_from(1, b"bbb").unwrap();
+	
+	// Every round drains 3 elements from lane 0 and 1 element from lane 1
+	assert_eq!(queue.pop_n(8).unwrap(), b"AAAbAAAb");
+	assert_eq!(queue.pop_n(8), Err(b"AAb".to_vec()));
+	
+	// Empty lanes forfeit their credit
+	queue.push_from(1, b"bb").unwrap();
+	assert_eq!(queue.pop_n(2).unwrap(), b"bb");
+	assert_eq!(queue.scheduling(), Scheduling::Weighted);
+}
+
+#[test]
+fn test_priority_limit() {
+	let mut queue = PrioritySliceQueue::with_limit(2, 8);
+	queue.set_reservation(0, 2);
+	assert_eq!(queue.reservation(0), 2);
+	assert_eq!((queue.remaining(0), queue.remaining(1)), (8, 6));
+	
+	// The bulk lane cannot use the reserved space
+	assert_eq!(queue.push_from(1, b"Testolope"), Err(6));
+	assert_eq!((queue.remaining(0), queue.remaining(1)), (2, 0));
+	assert_eq!(queue.push(1, b'!'), Err(b'!'));
+	queue.push_from(0, b"ct").unwrap();
+	assert_eq!(queue.push_n(0, b"l".to_vec()), Err(b"l".to_vec()));
+	
+	queue.drop_n(3).unwrap();
+	assert_eq!((queue.remaining(0), queue.remaining(1)), (3, 1));
+	assert_eq!(queue.limit(), 8);
+}
+
+
+#[test]
+#[should_panic(expected = "`lane` is out of range")]
+fn test_priority_lane_out_of_range() {
+	PrioritySliceQueue::new(2).push(2, 0u8).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "The reservations exceed the limit")]
+fn test_priority_reservations_exceed_limit() {
+	let mut queue = PrioritySliceQueue::<u8>::with_limit(2, 8);
+	queue.set_reservation(0, 4);
+	queue.set_reservation(1, 5);
+}
+
+#[test]
+#[should_panic(expected = "`limit` is smaller than the reservations")]
+fn test_priority_limit_below_reservations() {
+	let mut queue = PrioritySliceQueue::<u8>::with_limit(2, 8);
+	queue.set_reservation(1, 4);
+	queue.set_limit(3);
+}
@@ -0,0 +1,92 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::io::Read;
+
+
+#[test]
+fn test_in_order() {
+	let mut queue = ReassemblyQueue::new();
+	queue.insert(0, b"Test").unwrap();
+	queue.insert(4, b"olope").unwrap();
+	
+	let mut buf = Vec::new();
+	queue.read_to_end(&mut buf).unwrap();
+	assert_eq!(buf, b"Testolope");
+	assert_eq!(queue.consumed_total(), 9);
+}
+
+#[test]
+fn test_out_of_order() {
+	let mut queue = ReassemblyQueue::new();
+	queue.insert(7, b"pe").unwrap();
+	queue.insert(2, b"st").unwrap();
+	assert!(queue.is_empty());
+	assert_eq!(queue.holes(), vec![0..2, 4..7]);
+	assert_eq!(queue.pending_len(), 4);
+	
+	queue.insert(0, b"Te").unwrap();
+	assert_eq!(queue.peek_n(4), Ok(b"Test".as_ref()));
+	assert_eq!(queue.holes(), vec![4..7]);
+	
+	queue.insert(4, b"olo").unwrap();
+	assert_eq!(queue.pop_n(9).unwrap(), b"Testolope");
+	assert!(queue.holes().is_empty());
+	assert_eq!(queue.contiguous_end(), 9);
+}
+
+#[test]
+fn test_overlapping() {
+	let mut queue = ReassemblyQueue::new();
+	queue.insert(3, b"tol").unwrap();
+	queue.insert(8, b"e").unwrap();
+	queue.insert(5, b"lop").unwrap();
+	assert_eq!(queue.holes(), vec![0..3]);
+	assert_eq!(queue.pending_len(), 6);
+	
+	// Insert a segment that covers everything including already received bytes
+	queue.insert(0, b"Testolope").unwrap();
+	queue.insert(2, b"stolo").unwrap();
+	assert_eq!(queue.pop_n(9).unwrap(), b"Testolope");
+	assert_eq!(queue.len(), 0);
+}
+
+#[test]
+fn test_merge() {
+	let mut queue = ReassemblyQueue::new();
+	
+	// Grow a pending segment at its end and at its front
+	queue.insert(4, b"ol").unwrap();
+	queue.insert(6, b"ope").unwrap();
+	queue.insert(2, b"st").unwrap();
+	assert_eq!(queue.holes(), vec![0..2]);
+	assert_eq!(queue.pending_len(), 7);
+	
+	// The bytes that were stored first are kept
+	queue.insert(1, b"XXXXXXXXX!").unwrap();
+	queue.insert(0, b"T").unwrap();
+	assert_eq!(queue.pop_n(usize::MAX).unwrap_err(), b"TXstolopeX!");
+	
+	// Merge several segments after the new segment
+	queue.insert(12, b"a").unwrap();
+	queue.insert(14, b"b").unwrap();
+	queue.insert(11, b"cdefg").unwrap();
+	assert_eq!(queue.pop_n(usize::MAX).unwrap_err(), b"caebg");
+}
+
+
+#[test]
+fn test_window() {
+	let mut queue = ReassemblyQueue::with_limit(4);
+	assert_eq!(queue.insert(2, b"stol"), Err(4));
+	assert_eq!(queue.holes(), vec![0..2]);
+	assert_eq!(queue.insert(10, b"!"), Err(4));
+	assert_eq!(queue.pending_len(), 2);
+	
+	// Consuming bytes moves the window
+	queue.insert(0, b"Te").unwrap();
+	queue.drop_n(4).unwrap();
+	assert_eq!(queue.window_end(), 8);
+	queue.insert(4, b"olop").unwrap();
+	assert_eq!(queue.insert(8, b"e"), Err(8));
+	assert_eq!(queue.pop_n(4).unwrap(), b"olop");
+}
@@ -0,0 +1,53 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::{ thread, time::{ Duration, Instant } };
+
+
+#[test]
+fn test_select() {
+	let (control, data) = (SharedSliceQueue::<u8>::new(), SharedSliceQueue::<u32>::with_limit(4));
+	let queues: [(&dyn Selectable, Interest); 3] = [
+		(&control, Interest::Readable(1)), (&data, Interest::Readable(2)), (&data, Interest::Writeable(5))
+	];
+	
+	// Poll without waiting
+	assert_eq!(select(&queues, Some(Duration::from_secs(0))), None);
+	data.push(7).unwrap();
+	assert_eq!(select(&queues, Some(Duration::from_secs(0))), None);
+	data.push(4).unwrap();
+	assert_eq!(select(&queues, Some(Duration::from_secs(0))), Some(1));
+	
+	// Validate that the first ready queue is returned
+	control.push(0x01).unwrap();
+	assert_eq!(select(&queues, None), Some(0));
+	
+	// Validate writeability
+	assert_eq!(select(&[(&data as &dyn Selectable, Interest::Writeable(2))], None), Some(0));
+	assert_eq!(select(&[(&data as &dyn Selectable, Interest::Writeable(3))], Some(Duration::from_secs(0))), None);
+}
+
+#[test]
+fn test_select_wait() {
+	let (control, data) = (SharedSliceQueue::<u8>::new(), SharedSliceQueue::<u8>::new());
+	let (control_handle, data_handle) = (control.clone(), data.clone());
+	
+	// Push from another thread while waiting
+	let thread = thread::spawn(move || {
+		thread::sleep(Duration::from_millis(20));
+		data_handle.push_from(b"Test").unwrap();
+		thread::sleep(Duration::from_millis(20));
+		control_handle.push(0x01).unwrap();
+	});
+	let queues: [(&dyn Selectable, Interest); 2] = [(&control, Interest::Readable(1)), (&data, Interest::Readable(8))];
+	assert_eq!(select(&queues, Some(Duration::from_secs(10))), Some(0));
+	thread.join().unwrap();
+	assert_eq!(data.len(), 4);
+}
+
+#[test]
+fn test_select_timeout() {
+	let queue = SharedSliceQueue::<u8>::new();
+	let start = Instant::now();
+	assert_eq!(select(&[(&queue as &dyn Selectable, Interest::Readable(1))], Some(Duration::from_millis(30))), None);
+	assert!(start.elapsed() >= Duration::from_millis(30));
+}
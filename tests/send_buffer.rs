@@ -0,0 +1,65 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::io::Write;
+
+
+#[test]
+fn test_send_ack() {
+	let mut buffer = SendBuffer::new();
+	buffer.push(b"Testolope").unwrap();
+	buffer.write_all(b"!!").unwrap();
+	
+	assert_eq!(buffer.send(4), (0, b"Test".as_ref()));
+	assert_eq!(buffer.send(4), (4, b"olop".as_ref()));
+	assert_eq!((buffer.acked(), buffer.sent(), buffer.pushed()), (0, 8, 11));
+	assert_eq!((buffer.in_flight(), buffer.unsent()), (8, 3));
+	
+	assert_eq!(buffer.ack(4), Ok(4));
+	assert_eq!(buffer.ack(2), Ok(0));
+	assert_eq!((buffer.acked(), buffer.len()), (4, 7));
+	
+	assert_eq!(buffer.send(usize::MAX), (8, b"e!!".as_ref()));
+	assert_eq!(buffer.send(usize::MAX), (11, b"".as_ref()));
+	assert_eq!(buffer.ack(11), Ok(7));
+	assert!(buffer.is_empty());
+}
+
+#[test]
+fn test_retransmit() {
+	let mut buffer = SendBuffer::new();
+	buffer.push(b"Testolope").unwrap();
+	buffer.send(9);
+	buffer.ack(2).unwrap();
+	
+	assert_eq!(buffer.peek_from_abs(4, 3), Some(b"olo".as_ref()));
+	assert_eq!(buffer.peek_from_abs(2, 7), Some(b"stolope".as_ref()));
+	
+	buffer.rewind();
+	assert_eq!(buffer.send(3), (2, b"sto".as_ref()));
+}
+
+
+#[test]
+fn test_invalid_ranges() {
+	let mut buffer = SendBuffer::new();
+	buffer.push(b"Testolope").unwrap();
+	buffer.send(6);
+	buffer.ack(2).unwrap();
+	
+	assert_eq!(buffer.peek_from_abs(1, 2), None);
+	assert_eq!(buffer.peek_from_abs(4, 3), None);
+	assert_eq!(buffer.peek_from_abs(u64::MAX, 2), None);
+	assert_eq!(buffer.ack(7), Err(7));
+	assert_eq!(buffer.acked(), 2);
+}
+
+#[test]
+fn test_limit() {
+	let mut buffer = SendBuffer::with_limit(4);
+	assert_eq!(buffer.push(b"Testolope"), Err(4));
+	buffer.send(4);
+	assert_eq!(buffer.remaining(), 0);
+	
+	buffer.ack(4).unwrap();
+	assert_eq!(buffer.remaining(), 4);
+}
@@ -0,0 +1,83 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::thread;
+
+
+#[test]
+fn test_shared() {
+	let queue = SharedSliceQueue::with_limit(8);
+	let handle = queue.clone();
+	
+	// Push from another thread
+	thread::spawn(move || {
+		handle.push_from(b"Testolope").unwrap_err();
+		handle.push(b'!').unwrap_err();
+	}).join().unwrap();
+	assert_eq!((queue.len(), queue.remaining(), queue.limit()), (8, 0, 8));
+	
+	assert_eq!(queue.peek_n(4), Ok(b"Test".to_vec()));
+	assert_eq!(queue.pop(), Some(b'T'));
+	assert_eq!(queue.pop_n(3), Ok(b"est".to_vec()));
+	queue.drop_n(2).unwrap();
+	let mut buf = [0; 4];
+	assert_eq!(queue.pop_into(&mut buf), Err(2));
+	assert_eq!(&buf[..2], b"op");
+	assert!(queue.is_empty());
+	assert_eq!(queue.pop(), None);
+	
+	queue.set_limit(2);
+	assert_eq!(queue.push_n(b"Te!".to_vec()), Err(b"!".to_vec()));
+}
+
+
+#[cfg(target_os = "linux")]
+mod readiness {
+	use super::*;
+	use std::os::{ raw::{ c_int, c_short, c_ulong }, unix::io::AsRawFd };
+	
+	#[repr(C)]
+	struct PollFd {
+		fd: c_int,
+		events: c_short,
+		revents: c_short
+	}
+	extern "C" {
+		fn poll(fds: *mut PollFd, nfds: c_ulong, timeout: c_int) -> c_int;
+	}
+	const POLLIN: c_short = 0x1;
+	const POLLOUT: c_short = 0x4;
+	
+	/// Polls `fd` without blocking and returns whether it is readable and writeable
+	fn poll_now(fd: &impl AsRawFd) -> (bool, bool) {
+		let mut poll_fd = PollFd{ fd: fd.as_raw_fd(), events: POLLIN | POLLOUT, revents: 0 };
+		assert!(unsafe{ poll(&mut poll_fd, 1, 0) } >= 0);
+		(poll_fd.revents & POLLIN != 0, poll_fd.revents & POLLOUT != 0)
+	}
+	
+	#[test]
+	fn test_readiness() {
+		let queue = SharedSliceQueue::with_limit(8);
+		let readiness = queue.readiness(4).unwrap();
+		assert_eq!(readiness.threshold(), 4);
+		assert_eq!(poll_now(&readiness), (false, true));
+		
+		queue.push_from(b"Test").unwrap();
+		assert_eq!(poll_now(&readiness), (true, true));
+		queue.push_from(b"o").unwrap();
+		assert_eq!(poll_now(&readiness), (true, false));
+		
+		queue.drop_n(1).unwrap();
+		assert_eq!(poll_now(&readiness), (true, true));
+		queue.drop_n(4).unwrap();
+		assert_eq!(poll_now(&readiness), (false, true));
+		
+		// Validate that a handle created for a non-empty queue starts with the correct state
+		queue.push_from(b"Testolo").unwrap();
+		assert_eq!(poll_now(&queue.readiness(2).unwrap()), (true, false));
+		assert_eq!(poll_now(&readiness), (true, false));
+		
+		// Validate that dropped handles are ignored
+		drop(readiness);
+		queue.drop_n(7).unwrap();
+	}
+}
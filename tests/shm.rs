@@ -0,0 +1,158 @@
+#![cfg(target_os = "linux")]
+extern crate slice_queue;
+use slice_queue::*;
+use std::{ env, fs, io::{ ErrorKind, Read, Write }, path::PathBuf, process, thread };
+
+
+/// Creates a unique region path for `name` and removes stale files
+fn region_path(name: &str) -> PathBuf {
+	let path = env::temp_dir().join(format!("slice_queue-test-{}-{}.shm", process::id(), name));
+	let _ = fs::remove_file(&path);
+	path
+}
+
+
+#[test]
+fn test_shm() {
+	let path = region_path("shm");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	let mut consumer = ShmQueue::open(&path).unwrap();
+	assert!(producer.capacity() >= 1);
+	assert_eq!(consumer.capacity(), producer.capacity());
+	assert_eq!(consumer.limit(), producer.capacity());
+	
+	// Exchange bytes between both handles
+	producer.push_from(b"Testolope").unwrap();
+	producer.write_all(b"!?").unwrap();
+	assert_eq!(consumer.len(), 11);
+	let mut peeked = [0; 4];
+	consumer.peek_into(&mut peeked).unwrap();
+	assert_eq!(&peeked, b"Test");
+	assert_eq!(consumer.pop_n(4).unwrap(), b"Test");
+	consumer.drop_n(5).unwrap();
+	assert_eq!(consumer.pop(), Ok(b'!'));
+	
+	let mut buf = [0; 4];
+	assert_eq!(consumer.read(&mut buf).unwrap(), 1);
+	assert_eq!(&buf[..1], b"?");
+	assert!(consumer.is_empty());
+	assert_eq!((producer.pushed_total(), producer.consumed_total()), (11, 11));
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_shm_wrap_around() {
+	let path = region_path("wrap_around");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	let mut consumer = ShmQueue::open(&path).unwrap();
+	let capacity = producer.capacity();
+	
+	// Fill the ring, consume the first half and fill it again
+	let bytes: Vec<u8> = (0..capacity).map(|i| i as u8).collect();
+	producer.push_from(&bytes).unwrap();
+	assert_eq!(producer.remaining(), 0);
+	assert_eq!(producer.push(7), Err(7));
+	consumer.drop_n(capacity / 2).unwrap();
+	assert_eq!(producer.reserve_n(capacity / 2 + 1), Err(capacity / 2));
+	producer.push_n(bytes[..capacity / 2].to_vec()).unwrap();
+	
+	// Validate that the wrapped range is contiguous
+	let expected: Vec<u8> = bytes[capacity / 2..].iter().chain(bytes[..capacity / 2].iter()).cloned().collect();
+	assert_eq!(unsafe{ consumer.peek_raw(capacity) }.unwrap(), expected.as_slice());
+	assert_eq!(consumer.pop_n(capacity + 1), Err(expected));
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_shm_push_in_place() {
+	let path = region_path("push_in_place");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	let pushed = producer.push_in_place(8, |buf: &mut[u8]| -> Result<usize, ()> {
+		buf[..4].copy_from_slice(b"Test");
+		Ok(4)
+	});
+	assert_eq!(pushed, Ok(4));
+	let mut peeked = [0; 8];
+	assert_eq!(producer.peek_into(&mut peeked), Err(4));
+	assert_eq!(&peeked[..4], b"Test");
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_shm_peek() {
+	let path = region_path("peek");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	producer.push_from(b"Testolope").unwrap();
+	
+	// The shared bytes are never borrowed by the safe API
+	assert_eq!(producer.peek(), None);
+	assert_eq!(producer.peek_n(0), Ok(&[][..]));
+	assert_eq!(producer.peek_n(4), Err(&[][..]));
+	assert_eq!(producer.len(), 9);
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_shm_threads() {
+	let path = region_path("threads");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	let mut consumer = ShmQueue::open(&path).unwrap();
+	
+	// Stream more bytes than the capacity through the ring
+	let len = producer.capacity() * 4 + 7;
+	let thread = thread::spawn(move || {
+		let bytes: Vec<u8> = (0..len).map(|i| i as u8).collect();
+		let mut written = 0;
+		while written < len { written += producer.write(&bytes[written..]).unwrap() }
+	});
+	let mut received = Vec::new();
+	while received.len() < len {
+		let mut buf = [0; 1024];
+		let read = consumer.read(&mut buf).unwrap();
+		received.extend_from_slice(&buf[..read]);
+	}
+	thread.join().unwrap();
+	assert!(received.iter().enumerate().all(|(i, byte)| *byte == i as u8));
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_shm_open_file_cursor() {
+	let path = region_path("open_file_cursor");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	producer.push_from(b"Testolope").unwrap();
+	
+	// Move the cursor away from the header before attaching
+	let mut file = fs::OpenOptions::new().read(true).write(true).open(&path).unwrap();
+	file.read_exact(&mut [0; 16]).unwrap();
+	let consumer = ShmQueue::open_file(file).unwrap();
+	assert_eq!(unsafe{ consumer.peek_raw(usize::MAX) }, Err(b"Testolope".as_ref()));
+	fs::remove_file(&path).unwrap();
+}
+
+
+#[test]
+fn test_shm_open_uninitialized() {
+	let path = region_path("open_uninitialized");
+	fs::write(&path, b"Testolope").unwrap();
+	assert_eq!(ShmQueue::open(&path).unwrap_err().kind(), ErrorKind::InvalidData);
+	fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "`n` exceeds the remaining space")]
+fn test_shm_push_in_place_exceeds() {
+	let path = region_path("push_in_place_exceeds");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	fs::remove_file(&path).unwrap();
+	let _ = producer.push_in_place(producer.capacity() + 1, |_: &mut[u8]| -> Result<usize, ()> { Ok(0) });
+}
+
+#[test]
+#[should_panic(expected = "`push_fn` must not claim that it pushed more elements than `n`")]
+fn test_shm_push_in_place_overclaim() {
+	let path = region_path("push_in_place_overclaim");
+	let mut producer = ShmQueue::create(&path, 1).unwrap();
+	fs::remove_file(&path).unwrap();
+	let _ = producer.push_in_place(4, |_: &mut[u8]| -> Result<usize, ()> { Ok(5) });
+}
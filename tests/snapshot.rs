@@ -0,0 +1,103 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::io::ErrorKind;
+
+
+/// Creates a snapshot of a byte queue
+fn snapshot(data: &[u8], limit: usize) -> Vec<u8> {
+	let mut queue = SliceQueue::with_limit(limit);
+	queue.push_from(data).unwrap();
+	
+	let mut snapshot = Vec::new();
+	queue.save_to(&mut snapshot).unwrap();
+	snapshot
+}
+
+
+#[test]
+fn test_snapshot() {
+	let mut queue = SliceQueue::with_limit(16);
+	queue.push_from(b"Testolope").unwrap();
+	queue.drop_n(4).unwrap();
+	queue.set_auto_shrink_mode(AutoShrinkMode::Disabled);
+	
+	let mut snapshot = Vec::new();
+	queue.save_to(&mut snapshot).unwrap();
+	assert_eq!(snapshot.len(), 30 + 5 + 4);
+	assert_eq!(&snapshot[..4], b"SLQS");
+	
+	let restored: SliceQueue<u8> = SliceQueue::load_from(snapshot.as_slice()).unwrap();
+	assert_eq!(&restored[..], b"olope");
+	assert_eq!(restored.limit(), 16);
+	assert_eq!(restored.auto_shrink_mode(), AutoShrinkMode::Disabled);
+	assert_eq!(restored.consumed_total(), 0);
+}
+
+#[test]
+fn test_snapshot_unlimited() {
+	let restored: SliceQueue<u8> = SliceQueue::load_from(snapshot(b"", usize::MAX).as_slice()).unwrap();
+	assert!(restored.is_empty());
+	assert_eq!(restored.limit(), usize::MAX);
+}
+
+#[cfg(feature = "unsafe_fast_code")]
+#[test]
+fn test_snapshot_pod() {
+	let mut queue = SliceQueue::with_limit(8);
+	queue.push_from(&[0x0102_0304u32, 0xdead_beef, 7]).unwrap();
+	
+	let mut snapshot = Vec::new();
+	queue.save_to(&mut snapshot).unwrap();
+	assert_eq!(snapshot.len(), 30 + 12 + 4);
+	
+	let restored: SliceQueue<u32> = SliceQueue::load_from(snapshot.as_slice()).unwrap();
+	assert_eq!(&restored[..], &[0x0102_0304u32, 0xdead_beef, 7]);
+	assert_eq!(restored.limit(), 8);
+	
+	// Validate that the element size is checked
+	let err = SliceQueue::<u16>::load_from(snapshot.as_slice()).unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+
+#[test]
+fn test_snapshot_corrupted_header() {
+	let mut snapshot = snapshot(b"Testolope", 16);
+	snapshot[12] ^= 0x01;
+	let err = SliceQueue::<u8>::load_from(snapshot.as_slice()).unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_snapshot_corrupted_payload() {
+	let mut snapshot = snapshot(b"Testolope", 16);
+	snapshot[32] ^= 0x01;
+	let err = SliceQueue::<u8>::load_from(snapshot.as_slice()).unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_snapshot_invalid_magic() {
+	let mut snapshot = snapshot(b"Testolope", 16);
+	snapshot[..4].copy_from_slice(b"ABCD");
+	let err = SliceQueue::<u8>::load_from(snapshot.as_slice()).unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_snapshot_truncated() {
+	let snapshot = snapshot(b"Testolope", 16);
+	let err = SliceQueue::<u8>::load_from(&snapshot[..snapshot.len() - 1]).unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+	
+	let err = SliceQueue::<u8>::load_from(&snapshot[..10]).unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+}
+
+#[cfg(all(feature = "unsafe_fast_code", target_pointer_width = "64"))]
+#[test]
+fn test_snapshot_element_too_large() {
+	let queue = SliceQueue::<[u8; 1 << 32]>::new();
+	let err = queue.save_to(Vec::new()).unwrap_err();
+	assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
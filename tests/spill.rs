@@ -0,0 +1,145 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::io::{ Read, Write };
+
+
+#[test]
+fn test_spill() {
+	let mut queue = SpillQueue::new(4);
+	queue.push_from(b"Test").unwrap();
+	assert!(queue.spill_path().is_none());
+	
+	queue.write_all(b"olope").unwrap();
+	assert_eq!((queue.total_len(), queue.len(), queue.spilled_len()), (9, 4, 5));
+	let path = queue.spill_path().unwrap().to_path_buf();
+	assert!(path.exists());
+	
+	// Read the bytes back in order
+	assert_eq!(queue.pop_n(2).unwrap(), b"Te");
+	assert_eq!(queue.peek_n(4).unwrap(), b"stol");
+	assert_eq!(queue.peek_n(6).unwrap_err(), b"stol");
+	let mut buf = [0; 3];
+	queue.read_exact(&mut buf).unwrap();
+	assert_eq!(&buf, b"sto");
+	
+	// Append while bytes are spilled
+	queue.push_from(b"!?").unwrap();
+	let mut rest = Vec::new();
+	queue.read_to_end(&mut rest).unwrap();
+	assert_eq!(rest, b"lope!?");
+	assert!(queue.is_empty());
+	queue.flush().unwrap();
+	
+	// Validate that the file is deleted on drop
+	drop(queue);
+	assert!(!path.exists());
+}
+
+#[test]
+fn test_spill_traits() {
+	let mut queue = SpillQueue::new(2);
+	queue.push(b'T').unwrap();
+	queue.push_n(b"esto".to_vec()).unwrap();
+	assert_eq!(queue.push_in_place(4, |buf: &mut[u8]| -> Result<usize, ()> {
+		buf.copy_from_slice(b"lope");
+		Ok(4)
+	}), Ok(4));
+	assert_eq!((queue.len(), queue.spilled_len()), (2, 7));
+	
+	// Consuming calls refill the memory tier
+	assert_eq!(queue.drop_n(2), Ok(()));
+	assert_eq!(queue.pop(), Ok(b's'));
+	assert_eq!(queue.pop_n(3).unwrap_err(), b"to");
+	let mut buf = [0; 2];
+	queue.pop_into(&mut buf).unwrap();
+	assert_eq!(&buf, b"lo");
+	assert_eq!(queue.drop_n(usize::MAX), Err(2));
+	assert!(queue.is_empty());
+	
+	// The file tier is reused after it has been read back completely
+	queue.push_from(b"Testolope").unwrap();
+	assert_eq!((queue.len(), queue.spilled_len()), (2, 7));
+	let mut all = Vec::new();
+	queue.read_to_end(&mut all).unwrap();
+	assert_eq!(all, b"Testolope");
+}
+
+#[test]
+fn test_spill_codec() {
+	let mut queue = SpillQueue::new(4);
+	queue.push_u32_be(0x01020304).unwrap();
+	queue.push_u16_be(0x0506).unwrap();
+	assert_eq!(queue.spilled_len(), 2);
+	assert_eq!(queue.pop_u32_be(), Ok(0x01020304));
+	assert_eq!(queue.pop_u16_be(), Ok(0x0506));
+}
+
+#[test]
+fn test_spill_dir() {
+	let dir = std::env::temp_dir().join(format!("slice_queue-test-{}", std::process::id()));
+	std::fs::create_dir_all(&dir).unwrap();
+	
+	let mut queue = SpillQueue::new(1);
+	queue.set_spill_dir(&dir);
+	queue.push_from(b"Testolope").unwrap();
+	assert!(queue.spill_path().unwrap().starts_with(&dir));
+	let mut all = Vec::new();
+	queue.read_to_end(&mut all).unwrap();
+	assert_eq!(all, b"Testolope");
+	
+	drop(queue);
+	std::fs::remove_dir(&dir).unwrap();
+}
+
+
+#[test]
+fn test_spill_limit() {
+	let mut queue = SpillQueue::with_limit(2, 6);
+	assert_eq!(queue.push_from(b"Testolope"), Err(6));
+	assert_eq!(queue.remaining(), 0);
+	assert_eq!(queue.push(b'!'), Err(b'!'));
+	assert_eq!(queue.push_n(b"!?".to_vec()), Err(b"!?".to_vec()));
+	
+	assert_eq!(queue.drop_n(2), Ok(()));
+	assert_eq!(queue.remaining(), 2);
+	assert_eq!(queue.pop_n(9).unwrap_err(), b"st");
+	queue.flush().unwrap();
+}
+
+#[test]
+#[should_panic(expected = "`threshold` is `0`")]
+fn test_spill_threshold_zero() {
+	SpillQueue::new(0);
+}
+
+#[test]
+#[should_panic(expected = "`self.len() + n` is larger than `self.limit`")]
+fn test_spill_push_in_place_limit() {
+	let mut queue = SpillQueue::with_limit(2, 6);
+	let _ = queue.push_in_place(7, |_: &mut[u8]| -> Result<usize, ()> { Ok(0) });
+}
+
+#[test]
+fn test_spill_dir_invalid() {
+	let mut queue = SpillQueue::new(2);
+	queue.set_spill_dir("/this/directory/does/not/exist");
+	queue.push_from(b"Te").unwrap();
+	assert_eq!(queue.push_from(b"stolope"), Err(0));
+	assert_eq!(queue.total_len(), 2);
+	
+	// The error is reported once by `flush`
+	assert!(queue.flush().is_err());
+	queue.flush().unwrap();
+}
+
+#[test]
+fn test_spill_dir_invalid_partial() {
+	let mut queue = SpillQueue::new(4);
+	queue.set_spill_dir("/this/directory/does/not/exist");
+	
+	// The bytes that fit into memory are reported, so a retry does not duplicate them
+	assert_eq!(queue.push_from(b"Testolope"), Err(4));
+	assert!(queue.write_all(b"olope").is_err());
+	assert!(queue.flush().is_err());
+	assert_eq!(queue.pop_n(usize::MAX).unwrap_err(), b"Test");
+}
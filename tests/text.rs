@@ -0,0 +1,87 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::fmt::Write;
+
+
+#[test]
+fn test_pop_str() {
+	let mut queue = SliceQueue::from("Grüße 🦀".as_bytes());
+	
+	// "ü" and "ß" are two bytes long and must not be split
+	assert_eq!(queue.pop_str(3).unwrap(), "Gr");
+	assert_eq!(queue.pop_str(3).unwrap(), "ü");
+	assert_eq!(queue.pop_str(usize::MAX).unwrap(), "ße 🦀");
+	assert!(queue.is_empty());
+}
+
+#[test]
+fn test_pop_char() {
+	let mut queue = SliceQueue::from("a€🦀".as_bytes());
+	assert_eq!(queue.pop_char().unwrap(), Some('a'));
+	assert_eq!(queue.pop_char().unwrap(), Some('€'));
+	assert_eq!(queue.pop_char().unwrap(), Some('🦀'));
+	assert_eq!(queue.pop_char().unwrap(), None);
+}
+
+#[test]
+fn test_chunked() {
+	let bytes = "🦀!".as_bytes();
+	let mut queue = SliceQueue::new();
+	
+	// Push the crab byte by byte
+	for byte in &bytes[..3] {
+		queue.push(*byte).unwrap();
+		assert_eq!(queue.peek_str(), "");
+		assert_eq!(queue.pop_char().unwrap(), None);
+		assert_eq!(queue.pop_str(4).unwrap(), "");
+	}
+	queue.push_from(&bytes[3..]).unwrap();
+	assert_eq!(queue.peek_str(), "🦀!");
+	assert_eq!(queue.len(), 5);
+}
+
+#[test]
+fn test_write() {
+	let mut queue = SliceQueue::new();
+	write!(queue, "Testolope-{:02}", 7).unwrap();
+	assert_eq!(queue.peek_str(), "Testolope-07");
+}
+
+#[test]
+fn test_push_fmt() {
+	use std::io::Write;
+	let mut queue = SliceQueue::new();
+	queue.push_fmt(format_args!("Testolope-{:02}", 7)).unwrap();
+	queue.write_all(b"!").unwrap();
+	assert_eq!(queue.peek_str(), "Testolope-07!");
+}
+
+
+#[test]
+fn test_invalid() {
+	let mut queue = SliceQueue::from(b"ok\xffok".as_ref());
+	assert_eq!(queue.peek_str(), "ok");
+	assert_eq!(queue.pop_str(5).unwrap(), "ok");
+	
+	// Validate that nothing is consumed and skip the invalid byte
+	let error = queue.pop_char().unwrap_err();
+	assert_eq!(error.error_len(), Some(1));
+	assert!(queue.pop_str(5).is_err());
+	assert_eq!(queue.peek_str(), "");
+	queue.drop_n(1).unwrap();
+	assert_eq!(queue.pop_str(5).unwrap(), "ok");
+}
+
+#[test]
+fn test_write_limit() {
+	let mut queue = SliceQueue::with_limit(4);
+	assert!(write!(queue, "{}", 1234567).is_err());
+	assert!(queue.is_empty());
+	
+	// `write!` appends fragment by fragment, `push_fmt` is all-or-nothing
+	let number = 1234567;
+	assert!(write!(queue, "ab{}", number).is_err());
+	assert_eq!(queue.peek_str(), "ab");
+	assert!(queue.push_fmt(format_args!("{}", number)).is_err());
+	assert_eq!(queue.peek_str(), "ab");
+}
@@ -0,0 +1,77 @@
+extern crate slice_queue;
+use slice_queue::*;
+use std::{ cell::Cell, rc::Rc, time::{ Duration, Instant } };
+
+
+/// A clock that only advances manually
+#[derive(Clone)]
+struct FakeClock(Rc<Cell<Instant>>);
+impl FakeClock {
+	fn advance(&self, millis: u64) {
+		self.0.set(self.0.get() + Duration::from_millis(millis))
+	}
+}
+impl Clock for FakeClock {
+	fn now(&self) -> Instant {
+		self.0.get()
+	}
+}
+
+
+#[test]
+fn test_ttl() {
+	let clock = FakeClock(Rc::new(Cell::new(Instant::now())));
+	let start = clock.now();
+	let mut queue = ExpiringSliceQueue::with_clock(Duration::from_millis(100), clock.clone());
+	
+	// Push three batches at different times
+	queue.push_from(b"Test").unwrap();
+	queue.push(b'o').unwrap();
+	clock.advance(50);
+	queue.push_n(b"lope".to_vec()).unwrap();
+	clock.advance(30);
+	queue.push_from(b"!?").unwrap();
+	assert_eq!(queue.oldest(), Some(start));
+	assert_eq!(queue.next_expiry(), Some(start + Duration::from_millis(100)));
+	
+	// Nothing has expired yet
+	assert_eq!(queue.expire_now(), 0);
+	clock.advance(20);
+	assert_eq!(queue.expire_now(), 5);
+	assert_eq!(queue.peek_n(6).unwrap(), b"lope!?");
+	
+	// Validate that consumed elements are not counted
+	queue.drop_n(2).unwrap();
+	assert_eq!(queue.expire(start + Duration::from_millis(150)), 2);
+	assert_eq!(queue.oldest(), Some(start + Duration::from_millis(80)));
+	queue.pop().unwrap();
+	assert_eq!(queue.expire(start + Duration::from_secs(1)), 1);
+	assert!(queue.is_empty());
+	assert_eq!(queue.oldest(), None);
+}
+
+#[test]
+fn test_ttl_system_clock() {
+	let mut queue = ExpiringSliceQueue::new(Duration::from_secs(3600));
+	queue.push_from(b"Testolope").unwrap();
+	assert_eq!(queue.expire_now(), 0);
+	assert_eq!(queue.len(), 9);
+	
+	queue.set_ttl(Duration::from_secs(0));
+	assert_eq!(queue.ttl(), Duration::from_secs(0));
+	assert_eq!(queue.expire_now(), 9);
+}
+
+#[test]
+fn test_ttl_limit() {
+	let clock = FakeClock(Rc::new(Cell::new(Instant::now())));
+	let mut queue = ExpiringSliceQueue::with_clock(Duration::from_millis(10), clock.clone());
+	queue.set_limit(4);
+	assert_eq!(queue.push_from(b"Testolope"), Err(4));
+	assert_eq!(queue.push(b'!'), Err(b'!'));
+	
+	// Validate that the rejected elements do not get a timestamp
+	clock.advance(10);
+	assert_eq!(queue.expire_now(), 4);
+	assert_eq!(queue.oldest(), None);
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "unsafe_fast_code")]
+extern crate slice_queue;
+use slice_queue::*;
+
+
+/// Reads the `n` bytes of `queue`'s allocation that follow the stored elements
+#[cfg(feature = "deref")]
+fn vacated(queue: &SliceQueue<u8>, n: usize) -> &[u8] {
+	assert!(n <= queue.reserved());
+	unsafe{ std::slice::from_raw_parts(queue.as_ptr().add(queue.len()), n) }
+}
+
+
+#[test]
+fn test_zeroize() {
+	let mut queue = SliceQueue::new();
+	queue.set_zeroize(true);
+	assert!(queue.zeroize());
+	
+	// Push and pop using every path
+	queue.push_from(b"Testolope").unwrap();
+	queue.push(b'!').unwrap();
+	queue.push_n(b"??".to_vec()).unwrap();
+	queue.reserve_n(64).unwrap();
+	assert_eq!(queue.pop(), Ok(b'T'));
+	assert_eq!(queue.pop_n(2).unwrap(), b"es");
+	let mut buf = [0; 2];
+	queue.pop_into(&mut buf).unwrap();
+	queue.drop_n(2).unwrap();
+	assert_eq!(&queue[..], b"pe!??");
+	
+	queue.shrink_to_fit();
+	assert_eq!(&queue[..], b"pe!??");
+}
+
+#[cfg(feature = "deref")]
+#[test]
+fn test_zeroize_vacated() {
+	let mut queue = SliceQueue::with_capacity(16);
+	queue.set_auto_shrink_mode(AutoShrinkMode::Disabled);
+	queue.set_zeroize(true);
+	queue.push_from(b"Testolope").unwrap();
+	
+	queue.drop_n(4).unwrap();
+	assert_eq!(vacated(&queue, 4), &[0; 4]);
+	queue.pop().unwrap();
+	queue.pop_n(2).unwrap();
+	assert_eq!(vacated(&queue, 7), &[0; 7]);
+	assert_eq!(&queue[..], b"pe");
+}
+
+#[test]
+fn test_zeroize_history() {
+	let mut queue = SliceQueue::new();
+	queue.set_zeroize(true);
+	queue.set_history_limit(2);
+	for byte in b"Testolope" {
+		queue.push(*byte).unwrap();
+		queue.pop().unwrap();
+	}
+	assert_eq!(queue.history(2), Ok(b"pe".as_ref()));
+}
+
+#[test]
+fn test_zeroize_cast() {
+	let mut queue = SliceQueue::new();
+	queue.set_zeroize(true);
+	queue.push_from(&[1u8, 0, 0, 0]).unwrap();
+	
+	let queue: SliceQueue<u32> = queue.cast().unwrap();
+	assert!(queue.zeroize());
+	assert_eq!(&queue[..], &[u32::from_le_bytes([1, 0, 0, 0])]);
+}
+
+#[test]
+fn test_zeroizing_queue() {
+	let mut queue = ZeroizingSliceQueue::with_limit(16);
+	assert!(queue.zeroize());
+	queue.push_from(b"Testolope").unwrap();
+	assert_eq!(queue.pop_n(4).unwrap(), b"Test");
+	
+	// The queue can be passed on as `SliceQueue`
+	fn drain(queue: &mut SliceQueue<u8>) -> Vec<u8> {
+		queue.pop_n(usize::MAX).unwrap_err()
+	}
+	assert_eq!(drain(&mut queue), b"olope");
+	assert_eq!(queue.limit(), 16);
+}
+
+#[test]
+fn test_borrowed_elements() {
+	// `SliceQueue` does not implement `Drop`, so the borrowed elements may be dropped first
+	let mut queue = SliceQueue::new();
+	let byte = 7u8;
+	queue.push(&byte).unwrap();
+	assert_eq!(queue.pop(), Ok(&7));
+}